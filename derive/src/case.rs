@@ -0,0 +1,66 @@
+/// Splits an identifier into lowercase words, on underscores and on case changes.
+fn words(ident: &str) -> Vec<String> {
+    let ident = ident.strip_prefix("r#").unwrap_or(ident);
+    let chars = ident.chars().collect::<Vec<_>>();
+
+    let mut words = Vec::new();
+    let mut word = String::new();
+
+    for (i, c) in chars.iter().enumerate() {
+        if *c == '_' || *c == '-' {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+
+        if c.is_uppercase() && !word.is_empty() {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).map(|c| c.is_lowercase()).unwrap_or(false);
+
+            // "fooBar" and the "S" of "HTTPServer"
+            if previous.is_lowercase() || previous.is_numeric() || next_is_lower {
+                words.push(std::mem::take(&mut word));
+            }
+        }
+
+        word.extend(c.to_lowercase());
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+pub fn to_upper_camel_case(ident: &str) -> String {
+    words(ident).iter().map(|w| capitalize(w)).collect()
+}
+
+pub fn to_lower_camel_case(ident: &str) -> String {
+    let words = words(ident);
+    let mut words = words.iter();
+
+    match words.next() {
+        Some(first) => first.to_owned() + &words.map(|w| capitalize(w)).collect::<String>(),
+        None => String::new(),
+    }
+}
+
+pub fn to_snake_case(ident: &str) -> String {
+    words(ident).join("_")
+}
+
+pub fn to_kebab_case(ident: &str) -> String {
+    words(ident).join("-")
+}
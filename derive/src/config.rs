@@ -0,0 +1,319 @@
+use proc_macro2::{Ident, TokenStream};
+use proc_macro_error::{emit_error, SpanRange};
+use quote::{quote, ToTokens};
+use syn::{
+    bracketed,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token::{self, Comma},
+    ExprPath, LitStr, Path, Token,
+};
+
+use crate::{derive::error_duplicate, suggest::emit_unknown_name, value::ConfigValueAssignment};
+
+type ConfigParser = fn(&mut Config, ConfigValueAssignment, Vec<&str>);
+
+/// The configs which can be set on an attribute declaration, with the function parsing them.
+///
+/// `doc` holds the doc comments of the declaration, it isn't suggested to users.
+const KNOWN_CONFIGS: [(&str, ConfigParser); 12] = [
+    ("doc", |c, config, _| c.parse_documentation(config)),
+    ("function", Config::parse_function),
+    ("field", |c, config, path| parse_flag(&mut c.field, config, path)),
+    ("reverse", |c, config, path| parse_flag(&mut c.reverse, config, path)),
+    ("unique", |c, config, path| parse_flag(&mut c.unique, config, path)),
+    ("const_fn", |c, config, path| parse_flag(&mut c.const_fn, config, path)),
+    ("table", |c, config, path| parse_flag(&mut c.table, config, path)),
+    ("optional", |c, config, path| parse_flag(&mut c.optional, config, path)),
+    ("required", |c, config, path| parse_flag(&mut c.required, config, path)),
+    ("no_wrap", |c, config, path| parse_flag(&mut c.no_wrap, config, path)),
+    ("convert", Config::parse_convert),
+    ("wrap", Config::parse_wrap),
+];
+
+/// The conversions which can be set with the `convert` config.
+const KNOWN_CONVERSIONS: [&str; 1] = ["into"];
+
+macro_rules! unwrap_as {
+    ($expr: expr, as $type: path, $error: expr) => {
+        match $expr {
+            $type(value) => value,
+            _ => {
+                emit_error!($expr, $error);
+                return;
+            }
+        }
+    };
+}
+
+pub struct ConfigDeclarationList {
+    _pound: Token!(#),
+    _bracket: token::Bracket,
+    declarations: Punctuated<ConfigValueAssignment, Comma>,
+}
+
+impl ConfigDeclarationList {
+    pub fn parse_all(input: ParseStream) -> syn::Result<Vec<Self>> {
+        let mut all_declarations = Vec::new();
+        while input.peek(Token!(#)) {
+            all_declarations.push(input.call(ConfigDeclarationList::parse)?)
+        }
+
+        Ok(all_declarations)
+    }
+}
+
+impl Parse for ConfigDeclarationList {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let content;
+        Ok(Self {
+            _pound: input.parse()?,
+            _bracket: bracketed!(content in input),
+            declarations: content.parse_terminated(ConfigValueAssignment::parse)?,
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct Config {
+    comment: String,
+    function_name: Option<LitStr>,
+    field: Option<Path>,
+    reverse: Option<Path>,
+    unique: Option<Path>,
+    const_fn: Option<Path>,
+    table: Option<Path>,
+    optional: Option<Path>,
+    required: Option<Path>,
+    no_wrap: Option<Path>,
+    convert: Option<LitStr>,
+    wrap: Option<ExprPath>,
+}
+
+impl Config {
+    pub fn new(attributes: Vec<ConfigDeclarationList>) -> Self {
+        let mut self_ = Self::default();
+
+        for list in attributes {
+            for config in list.declarations {
+                let path = config
+                    .ident()
+                    .segments
+                    .iter()
+                    .map(|s| s.ident.to_string())
+                    .collect::<Vec<_>>();
+
+                let path = path.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+
+                let parser = match path.as_slice() {
+                    [name] => KNOWN_CONFIGS.iter().find(|(known, _)| known == name),
+                    _ => None,
+                };
+
+                match parser {
+                    Some((_, parse)) => parse(&mut self_, config, path),
+                    None => emit_unknown_name(
+                        SpanRange::from_tokens(config.ident()),
+                        "Unknown config.",
+                        &path.join("::"),
+                        &KNOWN_CONFIGS
+                            .iter()
+                            .map(|(name, _)| *name)
+                            .filter(|name| *name != "doc")
+                            .collect::<Vec<_>>(),
+                        "Known configs are",
+                    ),
+                }
+            }
+        }
+
+        if let (Some(optional), Some(required)) = (&self_.optional, &self_.required) {
+            error_duplicate!(
+                required, "`required` can't be used with `optional`.";
+                SpanRange::from_tokens(optional), "The attribute is declared optional here."
+            );
+        }
+
+        if let (Some(convert), Some(wrap)) = (&self_.convert, &self_.wrap) {
+            error_duplicate!(
+                wrap, "`wrap` can't be used with `convert`.";
+                SpanRange::from_tokens(convert), "The values are already converted here."
+            );
+        }
+
+        self_
+    }
+
+    fn parse_documentation(&mut self, attr: ConfigValueAssignment) {
+        if attr.value().is_none() {
+            emit_error!(attr.ident(), "Expected `doc = ...`");
+            return;
+        }
+
+        let lit =
+            unwrap_as!(attr.value().unwrap(), as syn::Expr::Lit, "Expected a literal expression.");
+        let str = unwrap_as!(&lit.lit, as syn::Lit::Str, "Expected a string literal.");
+
+        if !self.comment.is_empty() {
+            self.comment.push('\n');
+        }
+        self.comment += str.value().as_str();
+    }
+
+    fn parse_convert(&mut self, attr: ConfigValueAssignment, path_str: Vec<&str>) {
+        if attr.value().is_none() {
+            emit_error!(attr.ident(), "Expected `convert = ...`");
+            return;
+        }
+
+        let lit =
+            unwrap_as!(attr.value().unwrap(), as syn::Expr::Lit, "Expected a literal expression.");
+        let str = unwrap_as!(&lit.lit, as syn::Lit::Str, "Expected a string literal.");
+
+        if let Some(str2) = &self.convert {
+            error_duplicate!(
+                attr, "This config is already set." ;
+                SpanRange::from_tokens(str2), "Value for config `{}` is already set here.", path_str.join("::")
+            );
+            return;
+        }
+
+        if !KNOWN_CONVERSIONS.contains(&str.value().as_str()) {
+            emit_unknown_name(
+                SpanRange::from_tokens(str),
+                "Unknown conversion.",
+                &str.value(),
+                &KNOWN_CONVERSIONS,
+                "Known conversions are",
+            );
+            return;
+        }
+
+        self.convert = Some(str.to_owned());
+    }
+
+    fn parse_wrap(&mut self, attr: ConfigValueAssignment, path_str: Vec<&str>) {
+        if attr.value().is_none() {
+            emit_error!(attr.ident(), "Expected `wrap = ...`");
+            return;
+        }
+
+        let path = unwrap_as!(
+            attr.value().unwrap(),
+            as syn::Expr::Path,
+            "Expected the path of a function, like `Box::new`."
+        );
+
+        match &self.wrap {
+            Some(path2) => {
+                error_duplicate!(
+                    attr, "This config is already set." ;
+                    SpanRange::from_tokens(path2), "Value for config `{}` is already set here.", path_str.join("::")
+                );
+            }
+            None => self.wrap = Some(path.to_owned()),
+        }
+    }
+
+    fn parse_function(&mut self, attr: ConfigValueAssignment, path_str: Vec<&str>) {
+        if attr.value().is_none() {
+            emit_error!(attr.ident(), "Expected `function = ...`");
+            return;
+        }
+
+        let lit =
+            unwrap_as!(attr.value().unwrap(), as syn::Expr::Lit, "Expected a literal expression.");
+        let str = unwrap_as!(&lit.lit, as syn::Lit::Str, "Expected a string literal.");
+
+        match &self.function_name {
+            Some(str2) => {
+                error_duplicate!(
+                    attr, "This config is already set." ;
+                    SpanRange::from_tokens(str2), "Value for config `{}` is already set here.", path_str.join("::")
+                );
+            }
+            None => {
+                if str.value().is_empty() {
+                    emit_error!(str, "The function name must not be empty.");
+                    return;
+                }
+
+                self.function_name = Some(str.to_owned())
+            }
+        }
+    }
+
+    pub fn is_field(&self) -> bool {
+        self.field.is_some()
+    }
+
+    pub fn is_reverse(&self) -> bool {
+        self.reverse.is_some()
+    }
+
+    pub fn is_unique(&self) -> bool {
+        self.unique.is_some()
+    }
+
+    pub fn is_const_fn(&self) -> bool {
+        self.const_fn.is_some()
+    }
+
+    pub fn is_table(&self) -> bool {
+        self.table.is_some()
+    }
+
+    /// Whether the attribute is declared optional, or required, overriding the detection of
+    /// `Option` types.
+    pub fn optionality(&self) -> Option<bool> {
+        match (&self.optional, &self.required) {
+            (Some(_), _) => Some(true),
+            (_, Some(_)) => Some(false),
+            _ => None,
+        }
+    }
+
+    pub fn no_wrap(&self) -> Option<&Path> {
+        self.no_wrap.as_ref()
+    }
+
+    /// The function applied to the values of the attribute, set by the `convert` or `wrap` config.
+    pub fn conversion(&self) -> Option<TokenStream> {
+        if self.convert.is_some() {
+            return Some(quote!(::core::convert::Into::into));
+        }
+
+        self.wrap.as_ref().map(|path| path.to_token_stream())
+    }
+
+    pub fn comment(&self) -> &str {
+        self.comment.as_ref()
+    }
+
+    pub fn function_name(&self) -> Option<Ident> {
+        self.function_name
+            .as_ref()
+            .map(|l| Ident::new(&l.value(), l.span()))
+    }
+
+    pub fn function_name_lit(&self) -> Option<&LitStr> {
+        self.function_name.as_ref()
+    }
+}
+
+fn parse_flag(flag: &mut Option<Path>, attr: ConfigValueAssignment, path_str: Vec<&str>) {
+    if let Some(value) = attr.value() {
+        emit_error!(value, "Expected `{}` without value.", path_str.join("::"));
+        return;
+    }
+
+    match flag {
+        Some(path2) => {
+            error_duplicate!(
+                attr, "This config is already set." ;
+                SpanRange::from_tokens(path2), "Config `{}` is already set here.", path_str.join("::")
+            );
+        }
+        None => *flag = Some(attr.ident().to_owned()),
+    }
+}
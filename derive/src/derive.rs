@@ -0,0 +1,1814 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use proc_macro_error::{
+    abort, abort_if_dirty, diagnostic, emit_error, set_dummy, Diagnostic, Level, SpanRange,
+};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    ext::IdentExt,
+    parenthesized,
+    parse::Parse,
+    parse_quote,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token::{self, Comma},
+    DataStruct, DeriveInput, Expr, Fields, Generics, Ident, LitStr, Token, Type, Variant,
+    Visibility,
+};
+
+use crate::{
+    config::{Config, ConfigDeclarationList},
+    dynamic::attr_value_tokens,
+    items::{GeneratedItems, SkipList},
+    lifetime::has_elided_lifetime,
+    lint::lints_tokens,
+    opt::{extract_option_call_arg, extract_type_from_option, is_option_wrapped},
+    reference::{
+        substitute_fields_references, Reference, ReferenceProcessor, VariantProperties,
+        ALL_FIELDS, WHOLE_SELF,
+    },
+    suggest::{emit_unknown_name, name_list, similar_name},
+    value::{
+        AttributeValueAssignment, AttributeValueAssignmentTokenStream,
+        AttributeValueAssignmentTokens, ValueAssignment,
+    },
+};
+
+macro_rules! error_duplicate {
+    ($span1: expr, $error1: expr $(, $error1fragments: expr)*;
+     $span2: expr, $error2: expr $(, $error2fragments: expr)*) => {{
+        let span2: proc_macro_error::SpanRange = $span2;
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "help_span")] {
+                // the help only has its own span on nightly, so it keeps the location too
+                emit_error!(
+                    $span1, $error1 $(, $error1fragments)*;
+                    help = span2.first => $crate::derive::with_location(
+                        format!($error2 $(, $error2fragments)*),
+                        span2
+                    )
+                );
+            } else {
+                emit_error!(
+                    $span1, $error1 $(, $error1fragments)*;
+                    note = $crate::derive::with_location(
+                        format!($error2 $(, $error2fragments)*),
+                        span2
+                    )
+                );
+            }
+        }
+    }};
+}
+
+pub(crate) use error_duplicate;
+
+/// Appends the position of `span` to a note or help, so it can be found without a multi-span
+/// diagnostic.
+pub(crate) fn with_location(note: String, span: SpanRange) -> String {
+    let start = span.first.start();
+
+    // The position is unknown when the compiler doesn't expose it.
+    if start.line == 0 {
+        return note;
+    }
+
+    let note = note.strip_suffix('.').unwrap_or(&note);
+    format!("{} (line {}, column {}).", note, start.line, start.column + 1)
+}
+
+macro_rules! unwrap_opt_or_continue {
+    ($expr: expr) => {{
+        let res = $expr;
+        if res.is_none() {
+            continue;
+        }
+        res.unwrap()
+    }};
+}
+
+macro_rules! unwrap_or_continue {
+    ($expr: expr) => {{
+        match $expr {
+            Ok(value) => value,
+            Err(e) => {
+                emit_error!(e.span(), e);
+                continue;
+            }
+        }
+    }};
+
+    (no_emit $expr: expr) => {{
+        match $expr {
+            Ok(value) => value,
+            Err(_) => {
+                continue;
+            }
+        }
+    }};
+}
+
+macro_rules! unwrap_ {
+    ($expr: expr) => {{
+        match $expr {
+            Ok(value) => value,
+            Err(e) => return Err(e),
+        }
+    }};
+    ($expr: expr, $error: expr) => {{
+        match $expr {
+            Ok(value) => value,
+            Err(e) => {
+                let error = syn::Error::new(e.span(), $error);
+                return Err(error);
+            }
+        }
+    }};
+}
+
+// declared after the macros, which they use
+mod fields;
+mod lints;
+mod opt_out;
+mod resolve;
+mod reverse;
+mod table;
+
+use fields::FieldAttributes;
+use opt_out::{apply_opt_outs, extract_opt_outs};
+use resolve::resolve_attribute_references;
+
+struct ParenList<T> {
+    _paren: token::Paren,
+    elements: Punctuated<T, Comma>,
+}
+
+impl<T> ParenList<T> {
+    fn into_parts(self) -> (token::Paren, Punctuated<T, Comma>) {
+        (self._paren, self.elements)
+    }
+
+    fn from_parts(paren: token::Paren, elements: Vec<T>) -> Self {
+        Self {
+            _paren: paren,
+            elements: Punctuated::from_iter(elements),
+        }
+    }
+}
+
+impl<T: Parse> Parse for ParenList<T> {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let content;
+
+        Ok(Self {
+            _paren: parenthesized!(content in input),
+            elements: content.parse_terminated(T::parse)?,
+        })
+    }
+}
+
+impl<T: ToTokens> ToTokens for ParenList<T> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self._paren
+            .surround(tokens, |tokens2| self.elements.to_tokens(tokens2));
+    }
+}
+
+struct AttributeDeclaration {
+    attributes: Vec<ConfigDeclarationList>,
+    vis: Visibility,
+    ident: Ident,
+    _colon: Token!(:),
+    type_: Type,
+    default_value: Option<ValueAssignment>,
+}
+
+impl Parse for AttributeDeclaration {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            attributes: input.call(ConfigDeclarationList::parse_all)?,
+            vis: input.parse()?,
+            ident: input.parse()?,
+            _colon: input.parse()?,
+            type_: unwrap_!(input.parse(), "Expected a type."),
+            default_value: input.parse()?,
+        })
+    }
+}
+
+/// An element of the `#[attr(...)]` of a type.
+enum TypeAttrElement {
+    Declaration(Box<AttributeDeclaration>),
+    Skip(SkipList),
+}
+
+impl Parse for TypeAttrElement {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if SkipList::peek(input) {
+            input.parse().map(Self::Skip)
+        } else {
+            input
+                .parse()
+                .map(|declaration| Self::Declaration(Box::new(declaration)))
+        }
+    }
+}
+
+/// Formats a type the way it would be written by hand, since the token stream display puts
+/// spaces between every token.
+fn type_string(type_: &Type) -> String {
+    const REPLACEMENTS: [(&str, &str); 10] = [
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" <", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        ("& ", "&"),
+        ("( ", "("),
+        (" )", ")"),
+        ("[ ", "["),
+    ];
+
+    let mut string = type_.to_token_stream().to_string();
+    for (from, to) in REPLACEMENTS {
+        string = string.replace(from, to);
+    }
+
+    string.replace(" ]", "]").replace(" ;", ";")
+}
+
+fn attribute_info_tokens(vis: &Visibility, attributes: &[Attribute]) -> TokenStream {
+    let infos = attributes.iter().map(|a| a.info_tokens());
+
+    quote! {
+        /// Returns the description of the attributes declared on this type, in declaration order.
+        #vis fn attribute_info() -> &'static [::custom_attrs::AttributeInfo] {
+            const ATTRIBUTES: &[::custom_attrs::AttributeInfo] = &[#(#infos),*];
+            ATTRIBUTES
+        }
+    }
+}
+
+fn attr_tokens(vis: &Visibility, attributes: &[Attribute], generics: &Generics) -> TokenStream {
+    let arms = attributes.iter().map(|a| {
+        let name = a.ident.unraw().to_string();
+        let unsupported = quote!(::custom_attrs::AttrValue::Unsupported);
+
+        // the variants opting out of the attribute have no value
+        if !a.skipped.is_empty() {
+            let getter = a.try_function_name();
+            let value = attr_value_tokens(&a.type_, quote!(value), generics)
+                .unwrap_or_else(|| unsupported.to_owned());
+
+            return quote!(#name => self.#getter().ok().map(|value| #value),);
+        }
+
+        let getter = a.function_name();
+        let value = attr_value_tokens(&a.type_, quote!(self.#getter()), generics)
+            .unwrap_or(unsupported);
+
+        quote!(#name => Some(#value),)
+    });
+
+    quote! {
+        /// Returns the value of the attribute declared with this name, or `None` if there is no such attribute.
+        #vis fn attr(&self, name: &str) -> Option<::custom_attrs::AttrValue<'_>> {
+            match name {
+                #(#arms)*
+                _ => None,
+            }
+        }
+    }
+}
+
+fn fields_pattern(fields: &Fields, metadata: Option<&AttributeMetadata>) -> TokenStream {
+    match fields {
+        Fields::Named(named) => {
+            let new_named = named.named.iter().map(|n| n.ident.as_ref().unwrap());
+
+            if let Some(metadata) = metadata {
+                let new_named = new_named.map(|field_ident| {
+                    let match_ = metadata.references.iter().find(|ref_| {
+                        ref_.name() == field_ident.to_string().as_str() || ref_.name() == ALL_FIELDS
+                    });
+
+                    if match_.is_some() {
+                        quote!(#field_ident)
+                    } else {
+                        quote!(#field_ident: _)
+                    }
+                });
+
+                quote!({#(#new_named),*})
+            } else {
+                quote!({#(#new_named: _),*})
+            }
+        }
+        Fields::Unnamed(unnamed) => {
+            let new_named = unnamed
+                .unnamed
+                .iter()
+                .enumerate()
+                .map(|(i, _)| Some(format_ident!("_{}", i)));
+
+            quote!((#(#new_named),*))
+        }
+        Fields::Unit => quote!(),
+    }
+}
+
+/// Returns the errors of the fields referenced by a value which don't exist on `owner`, a variant
+/// or a struct.
+fn field_reference_errors(
+    owner: &Ident,
+    fields: &Fields,
+    metadata: &AttributeMetadata,
+) -> Vec<Diagnostic> {
+    let mut errors = Vec::new();
+
+    for reference in metadata.references.iter() {
+        if is_whole_variant(reference) || has_field(fields, reference.name()) {
+            continue;
+        }
+
+        let help = match fields {
+            Fields::Named(named) if !named.named.is_empty() => {
+                let names = named
+                    .named
+                    .iter()
+                    .map(|field| field.ident.as_ref().unwrap().to_string())
+                    .collect::<Vec<_>>();
+
+                match similar_name(reference.name(), names.iter().map(String::as_str)) {
+                    Some(name) => format!("Did you mean `{}` ?", name),
+                    None => format!("Available fields are {}.", name_list(&names)),
+                }
+            }
+            Fields::Unnamed(unnamed) if !unnamed.unnamed.is_empty() => {
+                match unnamed.unnamed.len() {
+                    1 => String::from("The only valid index is `0`."),
+                    count => format!("Valid indexes are `0` to `{}`.", count - 1),
+                }
+            }
+            _ => format!("`{}` has no fields.", owner),
+        };
+
+        for span in reference.spans() {
+            errors.push(diagnostic!(
+                span, Level::Error, "`{}` has no field `{}`.", owner, reference.name();
+                help = help
+            ));
+        }
+    }
+
+    errors
+}
+
+/// Whether the reference is `#self` alone or `#fields`, which are valid on any variant.
+fn is_whole_variant(reference: &Reference) -> bool {
+    reference.name() == WHOLE_SELF || reference.name() == ALL_FIELDS
+}
+
+fn has_field(fields: &Fields, name: &str) -> bool {
+    match fields {
+        Fields::Named(named) => named
+            .named
+            .iter()
+            .any(|field| field.ident.as_ref().unwrap() == name),
+        Fields::Unnamed(unnamed) => name
+            .parse::<usize>()
+            .map(|i| i < unnamed.unnamed.len())
+            .unwrap_or(false),
+        Fields::Unit => false,
+    }
+}
+
+fn unit_variants(all_variants: &Punctuated<Variant, Comma>) -> Vec<&Ident> {
+    all_variants
+        .iter()
+        .filter(|v| matches!(v.fields, Fields::Unit))
+        .map(|v| &v.ident)
+        .collect()
+}
+
+fn match_subject(all_variants: &Punctuated<Variant, Comma>) -> TokenStream {
+    // an empty enum can only be matched by value
+    if all_variants.is_empty() {
+        quote!(*self)
+    } else {
+        quote!(self)
+    }
+}
+
+/// How the values written by the user are turned into values of the type of the attribute.
+#[derive(Clone)]
+struct ValueConversion {
+    /// Whether the values are wrapped into `Some` when they aren't already.
+    some: bool,
+    /// The function applied to each value, set by the `convert` or `wrap` config.
+    function: Option<TokenStream>,
+}
+
+impl ValueConversion {
+    /// Applies the conversion function to a value, or to the value inside `Some` when the values
+    /// are wrapped automatically, as it is what would be wrapped otherwise.
+    fn convert(&self, value: &Expr) -> Expr {
+        let function = match &self.function {
+            Some(function) => function,
+            None => return value.to_owned(),
+        };
+
+        let apply = |value: &Expr| -> Expr {
+            syn::parse2(quote_spanned!(value.span()=> #function(#value))).unwrap()
+        };
+
+        match extract_option_call_arg(value) {
+            Some(inner) if self.some => {
+                let inner = apply(inner);
+                syn::parse2(quote_spanned!(value.span()=> Some(#inner))).unwrap()
+            }
+            // the value is `None`, there is nothing to convert
+            None if self.some && is_option_wrapped(value) => value.to_owned(),
+            _ => apply(value),
+        }
+    }
+
+    /// Whether a converted value must be wrapped into `Some`.
+    fn wraps(&self, value: &Expr) -> bool {
+        self.some && !is_option_wrapped(value)
+    }
+
+    fn to_tokens(&self, value: &Expr) -> TokenStream {
+        let value = self.convert(value);
+
+        if self.wraps(&value) {
+            quote!(Some(#value))
+        } else {
+            quote!(#value)
+        }
+    }
+}
+
+/// Binds a value to the type of its attribute, so a type mismatch is reported on the value
+/// written by the user rather than in the generated getter.
+///
+/// The value of an optional attribute set without `Some` is bound to the inner type, then wrapped.
+/// The inner type isn't known when the `Option` is hidden behind an alias, so the value is left
+/// to inference.
+fn typed_value(
+    value: &Expr,
+    type_: &Type,
+    conversion: &ValueConversion,
+    span: SpanRange,
+) -> TokenStream {
+    let value = conversion.convert(value);
+    let binding = quote_spanned!(span.first=> __custom_attrs_value);
+
+    let (binding, result) = if conversion.wraps(&value) {
+        let binding = match extract_type_from_option(type_) {
+            Some(inner) => quote_spanned!(span.first=> #binding: #inner),
+            None => binding.to_owned(),
+        };
+
+        (binding, quote_spanned!(span.first=> Some(__custom_attrs_value)))
+    } else {
+        (quote_spanned!(span.first=> #binding: #type_), binding)
+    };
+
+    quote_spanned! {span.first=>
+        {
+            let #binding = #value;
+            #result
+        }
+    }
+}
+
+struct AttributeValue {
+    variant: Ident,
+    value: Expr,
+    self_references: Option<AttributeMetadata>,
+    conversion: ValueConversion,
+    /// Whether the value references fields the variant doesn't have, so it can't be generated.
+    invalid_references: bool,
+}
+
+impl AttributeValue {
+    fn new(
+        variant: Ident,
+        conversion: ValueConversion,
+        value: Expr,
+        metadata: Option<AttributeMetadata>,
+    ) -> Self {
+        Self {
+            variant,
+            value,
+            self_references: metadata,
+            conversion,
+            invalid_references: false,
+        }
+    }
+
+    fn get_span(&self) -> SpanRange {
+        self.self_references
+            .as_ref()
+            .map(|r| r.value_real_span)
+            .unwrap_or(SpanRange::single_span(self.value.span()))
+    }
+
+    fn typed_value(&self, type_: &Type) -> TokenStream {
+        if self.invalid_references {
+            return quote!(unreachable!());
+        }
+
+        typed_value(&self.value, type_, &self.conversion, self.get_span())
+    }
+
+    fn to_tokens(&self, variant: &Variant, type_: &Type, fallible: bool) -> TokenStream {
+        let ident = &variant.ident;
+        let fields = if self.invalid_references {
+            quote!({ .. })
+        } else {
+            fields_pattern(&variant.fields, self.self_references.as_ref())
+        };
+
+        let value = ok_tokens(self.typed_value(type_), fallible);
+
+        quote! {
+            Self::#ident #fields => #value,
+        }
+    }
+}
+
+struct Attribute {
+    vis: Visibility,
+    ident: Ident,
+    required: bool,
+    type_: Type,
+    values: Vec<AttributeValue>,
+    default: Option<Expr>,
+    default_references: Option<AttributeMetadata>,
+    default_variant_references: Vec<Reference>,
+    default_attribute_references: Vec<Reference>,
+    /// The default values using `#attr` references, resolved for each variant taking them.
+    resolved_defaults: HashMap<Ident, (Expr, Option<AttributeMetadata>)>,
+    /// The variants opting out of the attribute.
+    skipped: Vec<Ident>,
+    /// Whether `iter_<attribute>` is generated, as the type can skip it.
+    generates_iter: bool,
+    /// The errors of the values referencing fields their variant doesn't have, emitted once the
+    /// getters are generated with a placeholder for these values.
+    reference_errors: Vec<Diagnostic>,
+    config: Config,
+}
+
+impl Attribute {
+    fn new(declaration: AttributeDeclaration) -> Option<Self> {
+        let type_ = declaration.type_;
+        let config = Config::new(declaration.attributes);
+
+        let required = match config.optionality() {
+            Some(optional) => !optional,
+            None => extract_type_from_option(&type_).is_none(),
+        };
+
+        if let Some(no_wrap) = config.no_wrap().filter(|_| required) {
+            emit_error!(
+                no_wrap, "`no_wrap` can only be used on optional attributes.";
+                help = "Use `optional` to declare `{}` optional.", declaration.ident
+            );
+        }
+
+        let default = match declaration.default_value {
+            Some(default) => Some(parse_default_value(&declaration.ident, default)?),
+            None => None,
+        };
+        let (
+            default,
+            default_references,
+            default_variant_references,
+            default_attribute_references,
+        ) = match default {
+            Some(default) => (
+                Some(default.value),
+                default.self_references,
+                default.variant_references,
+                default.attribute_references,
+            ),
+            None => (None, None, Vec::new(), Vec::new()),
+        };
+
+        Some(Self {
+            vis: declaration.vis,
+            ident: declaration.ident,
+            required,
+            type_,
+            values: Vec::new(),
+            default,
+            default_references,
+            default_variant_references,
+            default_attribute_references,
+            resolved_defaults: HashMap::new(),
+            skipped: Vec::new(),
+            generates_iter: true,
+            reference_errors: Vec::new(),
+            config,
+        })
+    }
+
+    /// Whether the values of the attribute are wrapped into `Some` when they aren't already.
+    fn wraps_values(&self) -> bool {
+        !self.required && self.config.no_wrap().is_none()
+    }
+
+    fn conversion(&self) -> ValueConversion {
+        ValueConversion {
+            some: self.wraps_values(),
+            function: self.config.conversion(),
+        }
+    }
+
+    fn function_name(&self) -> Ident {
+        self.config
+            .function_name()
+            .unwrap_or(format_ident!("get_{}", self.ident))
+    }
+
+    fn info_tokens(&self) -> TokenStream {
+        let name = self.ident.unraw().to_string();
+        let getter = self.getter_name().to_string();
+        let type_ = type_string(&self.type_);
+        let doc = self.config.comment();
+        let optional = !self.required;
+        let has_default = self.default.is_some();
+
+        quote! {
+            ::custom_attrs::AttributeInfo::__new(#name, #getter, #type_, #doc, #optional, #has_default)
+        }
+    }
+
+    fn constness(&self) -> TokenStream {
+        if self.config.is_const_fn() {
+            quote!(const)
+        } else {
+            quote!()
+        }
+    }
+
+    fn check_const_fn(&self, metadata: Option<&AttributeMetadata>) {
+        if let Some(metadata) = metadata.filter(|_| self.config.is_const_fn()) {
+            emit_error!(
+                metadata.value_real_span,
+                "Self references can't be used in the values of a `const_fn` attribute."
+            );
+        }
+    }
+
+    fn default_tokens(&self) -> TokenStream {
+        self.wrap_default(self.default.as_ref())
+    }
+
+    /// Whether the default value depends on the variant taking it.
+    fn has_variant_default(&self) -> bool {
+        self.default_references.is_some()
+            || !self.default_variant_references.is_empty()
+            || !self.default_attribute_references.is_empty()
+    }
+
+    /// Returns the default value of the attribute, with the `#variant` references resolved for
+    /// this variant.
+    fn variant_default(
+        &self,
+        all_variants: &Punctuated<Variant, Comma>,
+        variant: &Variant,
+    ) -> Option<Expr> {
+        let default = self.default.as_ref()?;
+
+        if let Some((resolved, _)) = self.resolved_defaults.get(&variant.ident) {
+            return Some(resolved.to_owned());
+        }
+
+        if self.default_variant_references.is_empty() && self.default_references.is_none() {
+            return Some(default.to_owned());
+        }
+
+        // the values which don't parse are reported by `check_variant_defaults`
+        substitute_variant_references(default, all_variants, variant).ok()
+    }
+
+    /// Checks that the default value is still an expression once the `#variant` references are
+    /// replaced for each variant taking it, like `Self::#variant.name` which gives `Self::"A"`.
+    fn check_variant_defaults(&self, all_variants: &Punctuated<Variant, Comma>) {
+        let default = match &self.default {
+            Some(default) if !self.default_variant_references.is_empty() => default,
+            _ => return,
+        };
+
+        let variants = all_variants
+            .iter()
+            .filter(|v| !self.is_set(&v.ident) && !self.skips(&v.ident));
+
+        for variant in variants {
+            if let Err(e) = substitute_variant_references(default, all_variants, variant) {
+                emit_error!(
+                    e.span(), "The default value of `{}` isn't a valid expression for `{}` : {}", self.ident, variant.ident, e;
+                    note = "The `#variant` references are replaced by literals."
+                );
+
+                return;
+            }
+        }
+    }
+
+    /// Binds the default value taken by a variant to the type of the attribute.
+    fn typed_default(
+        &self,
+        default: Option<Expr>,
+        metadata: Option<&AttributeMetadata>,
+    ) -> TokenStream {
+        let default = match default {
+            Some(default) => default,
+            None => return self.wrap_default(None),
+        };
+
+        let span = metadata
+            .map(|metadata| metadata.value_real_span)
+            .unwrap_or(SpanRange::single_span(default.span()));
+
+        typed_value(&default, &self.type_, &self.conversion(), span)
+    }
+
+    /// Converts a value of the attribute, and wraps it into `Some` if the attribute is optional
+    /// and the value isn't. The value is bound to the type of the attribute, as the expression
+    /// it is inlined into may not tell the type of a conversion.
+    fn wrap_value(&self, tokens: TokenStream) -> TokenStream {
+        match syn::parse2::<Expr>(tokens.to_owned()) {
+            Ok(value) if self.config.conversion().is_some() => {
+                typed_value(&value, &self.type_, &self.conversion(), SpanRange::call_site())
+            }
+            Ok(value) => self.conversion().to_tokens(&value),
+            _ => tokens,
+        }
+    }
+
+    /// Returns the self references of the default value taken by this variant.
+    fn variant_default_references(&self, variant: &Variant) -> Option<&AttributeMetadata> {
+        match self.resolved_defaults.get(&variant.ident) {
+            Some((_, metadata)) => metadata.as_ref(),
+            None => self.default_references.as_ref(),
+        }
+    }
+
+    fn wrap_default(&self, default: Option<&Expr>) -> TokenStream {
+        match default {
+            Some(value) => self.conversion().to_tokens(value),
+            None => {
+                if !self.required {
+                    quote!(None)
+                } else {
+                    quote!(unreachable!())
+                }
+            }
+        }
+    }
+
+    fn set(
+        &mut self,
+        variant: &Variant,
+        value: AttributeValueAssignment,
+        metadata: Option<AttributeMetadata>,
+    ) {
+        let match_ = self.values.iter().find(|v| v.variant == variant.ident);
+
+        if let Some(value2) = match_ {
+            error_duplicate!(
+                value, "The value is already set for this attribute.";
+                value2.get_span(), "First value of `{}` is set here.", self.ident
+            );
+
+            return;
+        }
+
+        let errors = match &metadata {
+            Some(metadata) => field_reference_errors(&variant.ident, &variant.fields, metadata),
+            None => Vec::new(),
+        };
+
+        let mut value = AttributeValue::new(
+            variant.ident.to_owned(),
+            self.conversion(),
+            value.into_value(),
+            metadata,
+        );
+        value.invalid_references = !errors.is_empty();
+
+        self.values.push(value);
+        self.reference_errors.extend(errors);
+    }
+
+    fn is_set(&self, variant: &Ident) -> bool {
+        self.values.iter().any(|v| &v.variant == variant)
+    }
+
+    fn is_missing(&self, variant: &Variant) -> bool {
+        self.required
+            && self.default.is_none()
+            && !self.is_set(&variant.ident)
+            && !self.skips(&variant.ident)
+    }
+
+    fn validate(&self, all_variants: &Punctuated<Variant, Comma>) {
+        for value in self.values.iter() {
+            self.check_const_fn(value.self_references.as_ref());
+        }
+        self.check_const_fn(self.default_references.as_ref());
+        self.check_default_references(all_variants);
+        self.check_variant_defaults(all_variants);
+        self.check_unit_self_references(all_variants);
+
+        if self.config.is_table() {
+            for variant in all_variants.iter().filter(|v| self.skips(&v.ident)) {
+                emit_error!(
+                    variant.ident, "`{}` opts out of `{}`, while it is stored in a table.", variant.ident, self.ident;
+                    help = "Every variant needs a value to be stored in a table."
+                );
+            }
+
+            for variant in all_variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    emit_error!(
+                        variant.ident, "`{}` has fields, while `{}` is stored in a table.", variant.ident, self.ident;
+                        help = "The `table` config can only be used on enums whose variants have no fields."
+                    );
+                }
+            }
+        }
+
+        if self.config.is_unique() {
+            // several `None` don't conflict, unless `from_<attr>(None)` has to pick a variant
+            self.check_unique_values(
+                all_variants,
+                all_variants.iter(),
+                "while it must be unique",
+                !self.config.is_reverse(),
+            );
+        } else if self.config.is_reverse() {
+            let reason = format!(
+                "so `{}` can't tell them apart",
+                self.reverse_function_name()
+            );
+
+            self.check_unique_values(
+                all_variants,
+                all_variants
+                    .iter()
+                    .filter(|v| matches!(v.fields, Fields::Unit)),
+                &reason,
+                false,
+            );
+        }
+    }
+
+    /// The values of the variants without fields are used outside of the getter by the `table`
+    /// and `reverse` configs, where there is no `self` to reference.
+    fn check_unit_self_references(&self, all_variants: &Punctuated<Variant, Comma>) {
+        let config = if self.config.is_table() {
+            "table"
+        } else if self.config.is_reverse() {
+            "reverse"
+        } else {
+            return;
+        };
+
+        let mut default_reported = false;
+        let unit_variants = all_variants
+            .iter()
+            .filter(|v| matches!(v.fields, Fields::Unit) && !self.skips(&v.ident));
+
+        for variant in unit_variants {
+            let metadata = match self.values.iter().find(|v| v.variant == variant.ident) {
+                Some(value) => value.self_references.as_ref(),
+                // the default value is reported once
+                None if default_reported => None,
+                None => {
+                    let metadata = self.variant_default_references(variant);
+                    default_reported = metadata.is_some();
+                    metadata
+                }
+            };
+
+            if let Some(metadata) = metadata {
+                emit_error!(
+                    metadata.value_real_span,
+                    "Self references can't be used in the values of a `{}` attribute.", config
+                );
+            }
+        }
+    }
+
+    fn check_default_references(&self, all_variants: &Punctuated<Variant, Comma>) {
+        let metadata = match &self.default_references {
+            Some(metadata) => metadata,
+            None => return,
+        };
+
+        for variant in all_variants {
+            if self.is_set(&variant.ident) || self.skips(&variant.ident) {
+                continue;
+            }
+
+            for reference in metadata.references.iter() {
+                if is_whole_variant(reference) || has_field(&variant.fields, reference.name()) {
+                    continue;
+                }
+
+                emit_error!(
+                    variant.ident,
+                    "`{}` has no field `{}`, referenced by the default value of `{}`.", variant.ident, reference.name(), self.ident;
+                    help = "Set the value of `{}` on this variant.", self.ident
+                );
+            }
+        }
+    }
+
+    fn to_tokens(&self, all_variants: &Punctuated<Variant, Comma>) -> TokenStream {
+        if self.config.is_table() {
+            return self.table_tokens(all_variants);
+        }
+
+        // the variants opting out of the attribute make the getter return a `Result`
+        let fallible = !self.skipped.is_empty();
+        let function_name = self.getter_name();
+        let name = self.ident.unraw().to_string();
+
+        let vis = &self.vis;
+        let type_ = if fallible {
+            let type_ = &self.type_;
+            quote!(::core::result::Result<#type_, ::custom_attrs::MissingAttr>)
+        } else {
+            self.type_.to_token_stream()
+        };
+        let arms = all_variants.iter().filter_map(|var| {
+            if self.skips(&var.ident) {
+                let ident = &var.ident;
+                let fields = fields_pattern(&var.fields, None);
+                let variant_name = ident.to_string();
+
+                return Some(quote! {
+                    Self::#ident #fields => ::core::result::Result::Err(
+                        ::custom_attrs::MissingAttr::__new(#variant_name, #name)
+                    ),
+                });
+            }
+
+            match self.values.iter().find(|v| v.variant == var.ident) {
+                Some(value) => Some(value.to_tokens(var, &self.type_, fallible)),
+                // the default value depends on the fields or the properties of each variant
+                None if self.has_variant_default() => {
+                    let ident = &var.ident;
+                    let references = self.variant_default_references(var);
+                    let fields = fields_pattern(&var.fields, references);
+                    let default = self.variant_default(all_variants, var);
+                    let default = ok_tokens(self.typed_default(default, references), fallible);
+
+                    Some(quote!(Self::#ident #fields => #default,))
+                }
+                None => None,
+            }
+        });
+
+        // variants without value take the default one, which is kept even when every
+        // variant is set so it is still type checked
+        let default = if self.has_variant_default() {
+            quote!()
+        } else if self.default.is_some() || !self.required {
+            let default = ok_tokens(self.typed_default(self.default.to_owned(), None), fallible);
+            quote! {
+                #[allow(unreachable_patterns)]
+                _ => #default,
+            }
+        } else {
+            quote!()
+        };
+
+        let matched = match_subject(all_variants);
+
+        let comment = if fallible {
+            format!(
+                "{}\n\nReturns an error on {}, which opt out of this attribute.",
+                self.config.comment(),
+                name_list(&self.skipped.iter().map(Ident::to_string).collect::<Vec<_>>())
+            )
+        } else {
+            self.config.comment().to_string()
+        };
+        let constness = self.constness();
+        let reverse = self.reverse_tokens(all_variants);
+        let iter = self.iter_tokens(all_variants);
+
+        quote! {
+            #[doc = #comment]
+            #vis #constness fn #function_name(&self) -> #type_ {
+                match #matched {
+                    #(#arms)*
+                    #default
+                }
+            }
+
+            #reverse
+            #iter
+        }
+    }
+
+    fn try_function_name(&self) -> Ident {
+        format_ident!("try_{}", self.function_name())
+    }
+
+    /// The name of the generated getter, which is the fallible one when some variants opt out
+    /// of the attribute.
+    fn getter_name(&self) -> Ident {
+        if self.skipped.is_empty() {
+            self.function_name()
+        } else {
+            self.try_function_name()
+        }
+    }
+
+    fn iter_tokens(&self, all_variants: &Punctuated<Variant, Comma>) -> TokenStream {
+        if !self.generates_iter {
+            return quote!();
+        }
+
+        let function_name = self.iter_function_name();
+        let getter_name = self.getter_name();
+
+        let vis = &self.vis;
+        let type_ = &self.type_;
+
+        let unit_variants = unit_variants(all_variants)
+            .into_iter()
+            .filter(|ident| !self.skips(ident))
+            .collect::<Vec<_>>();
+        let count = unit_variants.len();
+
+        let doc = format!(
+            "Returns an iterator over the variants without fields, with their `{}` attribute.",
+            self.ident
+        );
+
+        // the variants opting out are filtered out, so the fallible getter always succeeds
+        let iter = if self.skipped.is_empty() {
+            quote! {
+                variants.into_iter().map(|variant| {
+                    let value = variant.#getter_name();
+                    (variant, value)
+                })
+            }
+        } else {
+            quote! {
+                variants.into_iter().filter_map(|variant| {
+                    let value = variant.#getter_name().ok()?;
+                    Some((variant, value))
+                })
+            }
+        };
+
+        quote! {
+            #[doc = #doc]
+            #vis fn #function_name() -> impl Iterator<Item = (Self, #type_)> {
+                let variants: [Self; #count] = [#(Self::#unit_variants),*];
+
+                #iter
+            }
+        }
+    }
+
+    /// Whether `iter_<attribute>` can return the type of the attribute, which isn't the case when
+    /// it has an elided lifetime, as the function has no argument to borrow it from.
+    fn is_iterable(&self) -> bool {
+        !has_elided_lifetime(self.type_.to_token_stream())
+    }
+
+    fn iter_function_name(&self) -> Ident {
+        format_ident!("iter_{}", self.ident)
+    }
+
+    fn check_no_attribute_references(&self) {
+        for reference in self.default_attribute_references.iter() {
+            reference.emit_error("Attribute references can only be used in enum attributes.");
+        }
+    }
+
+    fn validate_struct(&self, ident: &Ident, fields: &Fields) {
+        self.check_const_fn(self.default_references.as_ref());
+
+        if let Some(metadata) = &self.default_references {
+            for error in field_reference_errors(ident, fields, metadata) {
+                error.emit();
+            }
+        }
+
+        for reference in self.default_variant_references.iter() {
+            reference.emit_error("Variant references can only be used in enum attributes.");
+        }
+        self.check_no_attribute_references();
+
+        if self.config.is_reverse() {
+            emit_error!(self.ident, "The `reverse` config can only be used on enums.");
+        }
+
+        if self.config.is_unique() {
+            emit_error!(self.ident, "The `unique` config can only be used on enums.");
+        }
+
+        if self.config.is_table() {
+            emit_error!(self.ident, "The `table` config can only be used on enums.");
+        }
+
+        if self.default.is_none() && self.required {
+            emit_error!(
+                self.ident, "Value not set for `{}`.", self.ident;
+                help = "Attributes of a struct take their value from the declaration : `{}: <type> = <value>`.", self.ident
+            );
+        }
+    }
+
+    fn to_struct_tokens(&self, fields: &Fields) -> TokenStream {
+        let function_name = self.function_name();
+
+        let vis = &self.vis;
+        let ident = &self.ident;
+        let type_ = &self.type_;
+        let value = substitute_fields_references(self.default_tokens(), fields);
+        let comment = self.config.comment();
+        let constness = self.constness();
+
+        match &self.default_references {
+            Some(metadata) => {
+                let fields = fields_pattern(fields, Some(metadata));
+
+                quote! {
+                    #[doc = #comment]
+                    #vis fn #function_name(&self) -> #type_ {
+                        let Self #fields = self;
+                        #value
+                    }
+                }
+            }
+            None => quote! {
+                #[doc = #comment]
+                #vis #constness fn #function_name(&self) -> #type_ {
+                    Self::#ident()
+                }
+
+                #[doc = #comment]
+                #vis #constness fn #ident() -> #type_ {
+                    #value
+                }
+            },
+        }
+    }
+}
+
+/// Replaces the `#variant` references of a value by the properties of `variant`.
+fn substitute_variant_references(
+    value: &Expr,
+    all_variants: &Punctuated<Variant, Comma>,
+    variant: &Variant,
+) -> syn::Result<Expr> {
+    let index = all_variants
+        .iter()
+        .position(|v| v.ident == variant.ident)
+        .unwrap();
+    let properties = VariantProperties::new(all_variants, index);
+
+    syn::parse2(properties.substitute(value.to_token_stream()))
+}
+
+/// A default value, with the references it contains.
+struct DefaultValue {
+    value: Expr,
+    self_references: Option<AttributeMetadata>,
+    variant_references: Vec<Reference>,
+    attribute_references: Vec<Reference>,
+}
+
+fn parse_default_value(ident: &Ident, value: ValueAssignment) -> Option<DefaultValue> {
+    let processor = ReferenceProcessor::parse(value.into_value().into_token_stream()).ok()?;
+    let (tokens, reference_lists, real_span) = processor.into_parts();
+
+    let value = match syn::parse2::<TrailingExpr>(tokens) {
+        Ok(value) => value.0,
+        Err(e) => {
+            emit_error!(e.span(), e);
+            return None;
+        }
+    };
+
+    let mut metadata = None;
+    let mut variant_references = Vec::new();
+    let mut attribute_references = Vec::new();
+    for reference_list in reference_lists {
+        match reference_list.name().name() {
+            "self" => {
+                metadata = Some(AttributeMetadata::new(
+                    ident.to_string(),
+                    reference_list.into_list(),
+                    real_span,
+                ))
+            }
+            "variant" => variant_references = reference_list.into_list(),
+            "attr" => attribute_references = reference_list.into_list(),
+            _ => reference_list.name().emit_error("Unknown reference."),
+        }
+    }
+
+    Some(DefaultValue {
+        value,
+        self_references: metadata,
+        variant_references,
+        attribute_references,
+    })
+}
+
+/// An expression followed by the comma added by [`ReferenceTokens`] to locate unexpected ends.
+struct TrailingExpr(Expr);
+
+impl Parse for TrailingExpr {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let expr = input.parse()?;
+        let _: Option<Comma> = input.parse()?;
+
+        Ok(Self(expr))
+    }
+}
+
+fn parse_type_attributes(attrs: &[syn::Attribute]) -> (Vec<Attribute>, GeneratedItems) {
+    let mut attribute_declarations = Vec::<AttributeDeclaration>::new();
+    let mut items = GeneratedItems::default();
+
+    for attr in attrs.iter() {
+        let attr_ident = unwrap_opt_or_continue!(attr.path.get_ident());
+
+        match attr_ident.to_string().as_str() {
+            "attr" => {
+                let element_list: ParenList<TypeAttrElement> =
+                    unwrap_or_continue!(syn::parse2(attr.tokens.to_owned()));
+
+                for element in element_list.elements {
+                    let declaration = match element {
+                        TypeAttrElement::Declaration(declaration) => *declaration,
+                        TypeAttrElement::Skip(list) => {
+                            items.add(list);
+                            continue;
+                        }
+                    };
+
+                    let match_ = attribute_declarations
+                        .iter()
+                        .find(|attr2| declaration.ident == attr2.ident);
+
+                    if let Some(declaration2) = match_ {
+                        error_duplicate!(
+                            declaration.ident, "This attribute is already declared.";
+                            SpanRange::from_tokens(&declaration2.ident),
+                            "`{}` is already declared here.", declaration2.ident
+                        );
+
+                        continue;
+                    }
+
+                    attribute_declarations.push(declaration);
+                }
+            }
+
+            _ => continue,
+        }
+    }
+
+    let attributes = attribute_declarations
+        .into_iter()
+        .filter_map(Attribute::new)
+        .collect();
+
+    (attributes, items)
+}
+
+fn check_for_conflicts(attrs: &[Attribute]) {
+    let mut before = HashMap::<&LitStr, &Attribute>::new();
+    for attr in attrs
+        .iter()
+        .filter(|a| a.config.function_name_lit().is_some())
+    {
+        let lit = attr.config.function_name_lit().unwrap();
+
+        if let Some((lit2, attr2)) = before.get_key_value(lit) {
+            error_duplicate!(
+                lit, "The attribute `{}` already use this function name.", attr2.ident;
+                SpanRange::from_tokens(lit2), "First use of `{}` here.", lit.value()
+            );
+
+            continue;
+        }
+
+        before.insert(lit, attr);
+    }
+}
+
+fn parse_value_attributes(attrs: &[syn::Attribute]) -> Vec<AttributeValueAssignment> {
+    let mut variant_attrs = Vec::new();
+
+    for attr in attrs {
+        let attr_ident = unwrap_opt_or_continue!(attr.path.get_ident());
+
+        match attr_ident.to_string().as_str() {
+            "attr" => {
+                let list: ParenList<AttributeValueAssignment> =
+                    unwrap_or_continue!(syn::parse2(attr.tokens.to_owned()));
+
+                variant_attrs.extend(list.elements);
+            }
+            _ => continue,
+        }
+    }
+
+    variant_attrs
+}
+
+#[derive(Clone)]
+struct AttributeMetadata {
+    attribute_ident: String,
+    value_real_span: SpanRange,
+    references: Vec<Reference>,
+}
+
+impl AttributeMetadata {
+    fn new(
+        attribute_ident: String,
+        references: Vec<Reference>,
+        value_real_span: SpanRange,
+    ) -> Self {
+        Self {
+            attribute_ident,
+            references,
+            value_real_span,
+        }
+    }
+}
+
+/// Wraps the value of a fallible getter into `Ok`.
+fn ok_tokens(value: TokenStream, fallible: bool) -> TokenStream {
+    if fallible {
+        quote!(::core::result::Result::Ok(#value))
+    } else {
+        value
+    }
+}
+
+fn emit_unknown_attribute(span: SpanRange, name: &str, attributes: &[Attribute]) {
+    let names = attributes
+        .iter()
+        .map(|a| a.ident.unraw().to_string())
+        .collect::<Vec<_>>();
+
+    emit_unknown_name(span, "Unknown attribute.", name, &names, "Declared attributes are");
+}
+
+/// Emits one error per variant, listing every required attribute it doesn't set.
+fn check_missing_values(attributes: &[Attribute], all_variants: &Punctuated<Variant, Comma>) {
+    for variant in all_variants {
+        let missing = attributes
+            .iter()
+            .filter(|attr| attr.is_missing(variant))
+            .map(|attr| attr.ident.to_string())
+            .collect::<Vec<_>>();
+
+        let snippet = missing
+            .iter()
+            .map(|name| format!("{} = ...", name))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        match missing.len() {
+            0 => (),
+            1 => emit_error!(
+                variant.ident, "Value not set for `{}`.", missing[0];
+                help = "Set it with `#[attr({})]`.", snippet
+            ),
+            _ => emit_error!(
+                variant.ident, "Values not set for {}.", name_list(&missing);
+                help = "Set them with `#[attr({})]`.", snippet
+            ),
+        }
+    }
+}
+
+fn expand_variant_attributes(
+    variant: &mut Variant,
+    properties: &VariantProperties,
+    has_repr: bool,
+) -> Vec<AttributeMetadata> {
+    let mut self_references = Vec::new();
+
+    for attr in variant.attrs.iter_mut() {
+        let attr_ident = unwrap_opt_or_continue!(attr.path.get_ident());
+
+        match attr_ident.to_string().as_str() {
+            "attr" => {
+                let list: ParenList<AttributeValueAssignmentTokens> =
+                    unwrap_or_continue!(syn::parse2(attr.tokens.to_owned()));
+
+                let (list_span, elements) = list.into_parts();
+
+                let mut new_elements = Vec::new();
+                for element in elements {
+                    let (ident, equal, value) = element.into_parts();
+
+                    let processor = unwrap_or_continue!(no_emit ReferenceProcessor::parse(value.into_token_stream()));
+                    let (tokens, reference_lists, real_span) = processor.into_parts();
+
+                    for reference_list in reference_lists {
+                        match reference_list.name().name() {
+                            "self" => self_references.push(AttributeMetadata::new(
+                                ident.to_string(),
+                                reference_list.into_list(),
+                                real_span,
+                            )),
+                            "variant" => {
+                                check_discriminant_references(&reference_list.into_list(), has_repr)
+                            }
+                            // resolved once the values of every attribute are known
+                            "attr" => (),
+                            _ => reference_list.name().emit_error("Unknown reference."),
+                        }
+                    }
+
+                    new_elements.push(AttributeValueAssignmentTokenStream::from_parts(
+                        ident,
+                        equal,
+                        properties.substitute(tokens),
+                    ))
+                }
+
+                let new_list = ParenList::<AttributeValueAssignmentTokenStream>::from_parts(
+                    list_span,
+                    new_elements,
+                );
+
+                attr.tokens = new_list.to_token_stream();
+            }
+            _ => continue,
+        }
+    }
+
+    self_references
+}
+
+fn check_discriminant_references(references: &[Reference], has_repr: bool) {
+    if has_repr {
+        return;
+    }
+
+    for reference in references.iter().filter(|r| r.name() == "discriminant") {
+        reference.emit_error(
+            "`#variant.discriminant` can only be used on enums with an integer `repr`.",
+        );
+    }
+}
+
+/// Whether the type has a `#[repr(<integer>)]` attribute, giving its variants a discriminant.
+fn has_int_repr(attrs: &[syn::Attribute]) -> bool {
+    const INT_REPRS: [&str; 12] = [
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ];
+
+    attrs
+        .iter()
+        .filter(|a| a.path.is_ident("repr"))
+        .any(|attr| {
+            attr.tokens
+                .to_string()
+                .split(|c: char| !c.is_alphanumeric())
+                .any(|word| INT_REPRS.contains(&word))
+        })
+}
+
+/// Checks that no getter of a struct has the name of the associated function generated for an
+/// attribute, which is named after the attribute.
+fn check_associated_function_conflicts(attrs: &[Attribute]) {
+    let associated = attrs
+        .iter()
+        .filter(|attr| attr.default_references.is_none())
+        .collect::<Vec<_>>();
+
+    for attr in attrs {
+        let function_name = attr.function_name();
+        let match_ = associated
+            .iter()
+            .find(|attr2| attr2.ident.unraw() == function_name.unraw());
+
+        if let Some(attr2) = match_ {
+            let span = match attr.config.function_name_lit() {
+                Some(lit) => SpanRange::from_tokens(lit),
+                None => SpanRange::from_tokens(&attr.ident),
+            };
+
+            emit_error!(
+                span, "The getter of `{}` has the same name as the associated function of `{}`.", attr.ident, attr2.ident;
+                help = "Struct attributes also generate an associated function named after the attribute, choose another function name."
+            );
+        }
+    }
+}
+
+/// Returns the names of the items generated next to the getters, which the type can skip.
+fn generated_item_names(attrs: &[Attribute], is_enum: bool) -> Vec<String> {
+    let mut names = Vec::new();
+
+    if is_enum {
+        names.push("VARIANTS".to_owned());
+        names.extend(
+            attrs
+                .iter()
+                .filter(|attr| attr.is_iterable())
+                .map(|attr| attr.iter_function_name().to_string()),
+        );
+    }
+    names.push("attribute_info".to_owned());
+    names.push("attr".to_owned());
+
+    names
+}
+
+/// Checks that no getter, nor associated function of a struct, has the name of an item generated
+/// next to the getters.
+fn check_generated_item_conflicts(attrs: &[Attribute], items: &GeneratedItems, is_enum: bool) {
+    let generated = generated_item_names(attrs, is_enum)
+        .into_iter()
+        .filter(|name| items.generates(name))
+        .collect::<Vec<_>>();
+
+    for attr in attrs {
+        let getter_name = attr.getter_name().unraw().to_string();
+        let span = match attr.config.function_name_lit() {
+            Some(lit) => SpanRange::from_tokens(lit),
+            None => SpanRange::from_tokens(&attr.ident),
+        };
+
+        if generated.contains(&getter_name) {
+            emit_error!(
+                span, "The getter of `{}` has the same name as the generated `{}`.", attr.ident, getter_name;
+                help = "Choose another function name, or skip `{0}` with `#[attr(skip({0}))]`.", getter_name
+            );
+        }
+
+        let name = attr.ident.unraw().to_string();
+        if !is_enum && attr.default_references.is_none() && generated.contains(&name) {
+            emit_error!(
+                attr.ident, "The associated function of `{}` has the same name as the generated `{}`.", attr.ident, name;
+                help = "Struct attributes generate an associated function named after the attribute, skip `{0}` with `#[attr(skip({0}))]`.", name
+            );
+        }
+    }
+}
+
+/// The parts of the derive input needed to derive the attributes of a struct, with the attributes
+/// declared on it.
+struct StructContext<'a> {
+    type_attrs: &'a [syn::Attribute],
+    vis: &'a Visibility,
+    ident: &'a Ident,
+    generics: &'a Generics,
+    attributes: Vec<Attribute>,
+    field_attributes: FieldAttributes,
+    items: GeneratedItems,
+}
+
+fn derive_struct_attrs(
+    context: StructContext,
+    data_struct: DataStruct,
+) -> proc_macro2::TokenStream {
+    let StructContext {
+        type_attrs,
+        vis,
+        ident,
+        generics,
+        attributes,
+        mut field_attributes,
+        items,
+    } = context;
+
+    field_attributes.add_fields(None, &data_struct.fields, &attributes);
+
+    for attr in attributes.iter() {
+        attr.validate_struct(ident, &data_struct.fields);
+    }
+    field_attributes.validate();
+
+    abort_if_dirty();
+
+    check_for_conflicts(&attributes);
+    check_associated_function_conflicts(&attributes);
+    items.validate(&generated_item_names(&attributes, false));
+    check_generated_item_conflicts(&attributes, &items, false);
+
+    abort_if_dirty();
+
+    let field_tokens = field_attributes.to_tokens(vis, ident, generics);
+    let attribute_info =
+        items.item_tokens("attribute_info", attribute_info_tokens(vis, &attributes));
+    let attr = items.item_tokens("attr", attr_tokens(vis, &attributes, generics));
+    let (impl_generics, generics, generic_where) = generics.split_for_impl();
+    let tokens = attributes
+        .iter()
+        .map(|a| a.to_struct_tokens(&data_struct.fields));
+    // a struct is a single variant, so only the lints about the declarations apply
+    let lints = attributes.iter().map(|a| a.lint_tokens(&Punctuated::new()));
+    let field_lints = field_attributes.lint_tokens();
+    let lints = lints_tokens(type_attrs, quote!(#(#lints)* #field_lints));
+
+    quote! {
+        impl #impl_generics #ident #generics #generic_where {
+            #attribute_info
+            #attr
+
+            #(#tokens)*
+        }
+
+        #field_tokens
+        #lints
+    }
+}
+
+pub fn derive_custom_attrs(input: DeriveInput) -> proc_macro2::TokenStream {
+    let (attributes, items) = parse_type_attributes(&input.attrs);
+    let (field_attributes, mut attributes): (Vec<_>, Vec<_>) = attributes
+        .into_iter()
+        .partition(|attr| attr.config.is_field());
+    let mut field_attributes = FieldAttributes::new(&input.ident, field_attributes);
+
+    abort_if_dirty();
+
+    let mut data_enum = match input.data {
+        syn::Data::Struct(data_struct) => {
+            let context = StructContext {
+                type_attrs: &input.attrs,
+                vis: &input.vis,
+                ident: &input.ident,
+                generics: &input.generics,
+                attributes,
+                field_attributes,
+                items,
+            };
+
+            return derive_struct_attrs(context, data_struct);
+        }
+        syn::Data::Union(union_) => abort!(union_.union_token, "Not implemented for unions."),
+
+        syn::Data::Enum(data_enum) => data_enum,
+    };
+
+    let has_repr = has_int_repr(&input.attrs);
+    for attr in attributes.iter() {
+        check_discriminant_references(&attr.default_variant_references, has_repr);
+    }
+
+    let properties = (0..data_enum.variants.len())
+        .map(|i| VariantProperties::new(&data_enum.variants, i))
+        .collect::<Vec<_>>();
+
+    for (variant, properties) in data_enum.variants.iter_mut().zip(properties.iter()) {
+        let opt_outs = extract_opt_outs(variant);
+        let mut self_references = expand_variant_attributes(variant, properties, has_repr);
+        let variant_attrs = parse_value_attributes(&variant.attrs);
+
+        for attr in variant_attrs {
+            let opt = attributes
+                .iter_mut()
+                .find(|attr2| &attr2.ident == attr.ident());
+
+            if opt.is_none() {
+                if field_attributes.contains(attr.ident()) {
+                    emit_error!(
+                        attr.ident(),
+                        "`{}` is a field attribute, it can only be set on fields.",
+                        attr.ident()
+                    );
+                } else {
+                    emit_unknown_attribute(
+                        SpanRange::from_tokens(attr.ident()),
+                        &attr.ident().unraw().to_string(),
+                        &attributes,
+                    );
+                }
+                continue;
+            }
+
+            #[allow(clippy::cmp_owned)]
+            let match_ = self_references
+                .iter()
+                .enumerate()
+                .find(|(_, r)| r.attribute_ident == attr.ident().to_string())
+                .map(|(i, _)| i);
+
+            let metadata = match_.map(|i| self_references.swap_remove(i));
+            opt.unwrap().set(variant, attr, metadata)
+        }
+
+        apply_opt_outs(&mut attributes, &field_attributes, variant, opt_outs);
+        field_attributes.add_fields(Some(&variant.ident), &variant.fields, &attributes);
+    }
+
+    resolve_attribute_references(&mut attributes, &data_enum.variants);
+
+    check_missing_values(&attributes, &data_enum.variants);
+
+    for attr in attributes.iter() {
+        attr.validate(&data_enum.variants);
+    }
+    field_attributes.validate();
+
+    abort_if_dirty();
+
+    check_for_conflicts(&attributes);
+    items.validate(&generated_item_names(&attributes, true));
+    check_generated_item_conflicts(&attributes, &items, true);
+
+    abort_if_dirty();
+
+    for attr in attributes.iter_mut() {
+        attr.generates_iter =
+            attr.is_iterable() && items.generates(&attr.iter_function_name().to_string());
+    }
+
+    // emitted along the generated items instead of aborting, so the uses of the getters don't fail
+    // too, which means they are only reported once the other errors are fixed
+    let reference_errors = attributes
+        .iter_mut()
+        .flat_map(|attr| attr.reference_errors.drain(..))
+        .collect::<Vec<_>>();
+
+    let ident = &input.ident;
+    let field_tokens = field_attributes.to_tokens(&input.vis, ident, &input.generics);
+    let (impl_generics, generics, generic_where) = input.generics.split_for_impl();
+
+    let attribute_info = items.item_tokens(
+        "attribute_info",
+        attribute_info_tokens(&input.vis, &attributes),
+    );
+    let attr = items.item_tokens(
+        "attr",
+        attr_tokens(&input.vis, &attributes, &input.generics),
+    );
+    let tokens = attributes.iter().map(|a| a.to_tokens(&data_enum.variants));
+    let lints = attributes.iter().map(|a| a.lint_tokens(&data_enum.variants));
+    let field_lints = field_attributes.lint_tokens();
+    let lints = lints_tokens(&input.attrs, quote!(#(#lints)* #field_lints));
+
+    // a slice of `Self` can only be `'static` if `Self` is, which generic enums have to require
+    let variants = if items.generates("VARIANTS") {
+        let vis = &input.vis;
+        let unit_variants = unit_variants(&data_enum.variants);
+
+        let mut static_generics = input.generics.clone();
+        static_generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(Self: 'static));
+        let static_where = &static_generics.where_clause;
+
+        quote! {
+            impl #impl_generics #ident #generics #static_where {
+                /// The variants without fields, in declaration order.
+                #vis const VARIANTS: &'static [Self] = &[#(Self::#unit_variants),*];
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let tokens = quote! {
+        impl #impl_generics #ident #generics #generic_where {
+            #attribute_info
+            #attr
+
+            #(#tokens)*
+        }
+
+        #variants
+        #field_tokens
+        #lints
+    };
+
+    // the errors discard the returned tokens, but not the dummy ones
+    if !reference_errors.is_empty() {
+        set_dummy(tokens.clone());
+    }
+    for error in reference_errors {
+        error.emit();
+    }
+
+    tokens
+}
@@ -0,0 +1,195 @@
+use proc_macro2::TokenStream;
+use proc_macro_error::{emit_error, SpanRange};
+use quote::{format_ident, quote};
+use syn::{
+    ext::IdentExt, parse_quote, punctuated::Punctuated, spanned::Spanned, token::Comma, Fields,
+    Generics, Ident, Variant, Visibility,
+};
+
+use crate::case::to_upper_camel_case;
+
+use super::{
+    attr_tokens, attribute_info_tokens, check_for_conflicts, check_missing_values,
+    emit_unknown_attribute, parse_value_attributes, Attribute,
+};
+
+pub(super) struct FieldAttributes {
+    ident: Ident,
+    variants: Punctuated<Variant, Comma>,
+    names: Vec<String>,
+    attributes: Vec<Attribute>,
+}
+
+impl FieldAttributes {
+    pub(super) fn new(type_ident: &Ident, attributes: Vec<Attribute>) -> Self {
+        Self {
+            ident: format_ident!("{}Field", type_ident),
+            variants: Punctuated::new(),
+            names: Vec::new(),
+            attributes,
+        }
+    }
+
+    pub(super) fn add_fields(
+        &mut self,
+        variant: Option<&Ident>,
+        fields: &Fields,
+        others: &[Attribute],
+    ) {
+        // the companion enum is only generated when field attributes are declared
+        if self.attributes.is_empty() {
+            return;
+        }
+
+        for (i, field) in fields.iter().enumerate() {
+            let (name, span) = match &field.ident {
+                Some(ident) => (ident.unraw().to_string(), ident.span()),
+                None => (i.to_string(), field.ty.span()),
+            };
+
+            let camel_name = to_upper_camel_case(&name);
+            let ident = match (variant, &field.ident) {
+                (Some(variant), _) => format_ident!("{}{}", variant, camel_name, span = span),
+                (None, Some(_)) => format_ident!("{}", camel_name, span = span),
+                (None, None) => format_ident!("Field{}", camel_name, span = span),
+            };
+
+            let qualified_name = match variant {
+                Some(variant) => format!("{}.{}", variant, name),
+                None => name,
+            };
+
+            let match_ = self
+                .variants
+                .iter()
+                .zip(self.names.iter())
+                .find(|(variant2, _)| variant2.ident == ident);
+
+            if let Some((variant2, name2)) = match_ {
+                error_duplicate!(
+                    span, "The companion variant `{}` of `{}` is already generated for `{}`.", ident, qualified_name, name2;
+                    SpanRange::from_tokens(&variant2.ident), "`{}` is declared here.", name2
+                );
+
+                continue;
+            }
+
+            let companion_variant: Variant = parse_quote!(#ident);
+
+            for value in parse_value_attributes(&field.attrs) {
+                let opt = self
+                    .attributes
+                    .iter_mut()
+                    .find(|attr2| &attr2.ident == value.ident());
+
+                match opt {
+                    Some(attr) => attr.set(&companion_variant, value, None),
+                    None if others.iter().any(|attr| &attr.ident == value.ident()) => emit_error!(
+                        value.ident(),
+                        "`{}` isn't a field attribute, it can't be set on fields.",
+                        value.ident()
+                    ),
+                    None => emit_unknown_attribute(
+                        SpanRange::from_tokens(value.ident()),
+                        &value.ident().unraw().to_string(),
+                        &self.attributes,
+                    ),
+                }
+            }
+
+            self.variants.push(companion_variant);
+            self.names.push(qualified_name);
+        }
+    }
+
+    pub(super) fn contains(&self, ident: &Ident) -> bool {
+        self.attributes.iter().any(|attr| &attr.ident == ident)
+    }
+
+    pub(super) fn validate(&self) {
+        check_missing_values(&self.attributes, &self.variants);
+
+        for attr in self.attributes.iter() {
+            attr.check_no_attribute_references();
+            attr.validate(&self.variants);
+        }
+
+        check_for_conflicts(&self.attributes);
+    }
+
+    pub(super) fn to_tokens(
+        &self,
+        vis: &Visibility,
+        type_ident: &Ident,
+        generics: &Generics,
+    ) -> TokenStream {
+        if self.attributes.is_empty() {
+            return quote!();
+        }
+
+        let ident = &self.ident;
+        let variants = self.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+        let names = &self.names;
+        let count = variants.len();
+
+        let getters = self.attributes.iter().map(|a| a.to_tokens(&self.variants));
+        let type_functions = self.attributes.iter().map(|a| {
+            let vis = &a.vis;
+            let function_name = format_ident!("field_{}", a.ident);
+            let getter_name = a.function_name();
+            let type_ = &a.type_;
+            let comment = a.config.comment();
+            let constness = a.constness();
+
+            quote! {
+                #[doc = #comment]
+                #vis #constness fn #function_name(field: #ident) -> #type_ {
+                    field.#getter_name()
+                }
+            }
+        });
+
+        let attribute_info = attribute_info_tokens(vis, &self.attributes);
+        let attr = attr_tokens(vis, &self.attributes, &Generics::default());
+
+        let doc = format!("The fields of [`{}`], with their attributes.", type_ident);
+        let (impl_generics, generics, generic_where) = generics.split_for_impl();
+
+        quote! {
+            #[doc = #doc]
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+            #vis enum #ident {
+                #(#variants),*
+            }
+
+            impl #ident {
+                /// Returns the name of the field, prefixed by the name of its variant and a dot on enums.
+                pub const fn name(&self) -> &'static str {
+                    match *self {
+                        #(Self::#variants => #names),*
+                    }
+                }
+
+                #attribute_info
+                #attr
+
+                #(#getters)*
+            }
+
+            impl #impl_generics #type_ident #generics #generic_where {
+                /// Returns an iterator over the fields, with their name and their attributes.
+                #vis fn field_attrs() -> impl Iterator<Item = (&'static str, #ident)> {
+                    let fields: [#ident; #count] = [#(#ident::#variants),*];
+                    fields.into_iter().map(|field| (field.name(), field))
+                }
+
+                #(#type_functions)*
+            }
+        }
+    }
+
+    pub(super) fn lint_tokens(&self) -> TokenStream {
+        let lints = self.attributes.iter().map(|a| a.lint_tokens(&self.variants));
+        quote!(#(#lints)*)
+    }
+}
@@ -0,0 +1,127 @@
+use proc_macro2::TokenStream;
+use proc_macro_error::SpanRange;
+use quote::{quote, ToTokens};
+use syn::{punctuated::Punctuated, token::Comma, Expr, Variant};
+
+use crate::{
+    lint::{warning_tokens, Lint},
+    opt::{extract_option_call_arg, extract_type_from_option},
+    reference::has_placeholders,
+};
+
+use super::{type_string, Attribute};
+
+impl Attribute {
+    /// Warnings about values which compile, but are redundant or likely a mistake.
+    pub(super) fn lint_tokens(&self, all_variants: &Punctuated<Variant, Comma>) -> TokenStream {
+        let mut warnings = Vec::new();
+        let mut warn = |lint, span: SpanRange, message: String| {
+            warnings.push(warning_tokens(lint, span.first, &message))
+        };
+        let some_message = format!(
+            "The values of `{}` are wrapped into `Some` automatically.",
+            self.ident
+        );
+
+        if !self.required && self.default.is_none() && self.values.is_empty() {
+            warn(
+                Lint::UnusedAttribute,
+                SpanRange::from_tokens(&self.ident),
+                format!("`{}` is never set, so it is always `None`.", self.ident),
+            );
+        }
+
+        // the `Some` can't be added automatically when the value is itself an `Option`
+        let auto_wrapped = self.wraps_values()
+            && extract_type_from_option(&self.type_)
+                .and_then(extract_type_from_option)
+                .is_none();
+        let redundant_some =
+            |value: &Expr| auto_wrapped && extract_option_call_arg(value).is_some();
+
+        if let Some(default) = self.default.as_ref().filter(|default| redundant_some(default)) {
+            warn(
+                Lint::RedundantSome,
+                SpanRange::from_tokens(default),
+                some_message.to_owned(),
+            );
+        }
+
+        let default = self
+            .default
+            .as_ref()
+            .filter(|_| !self.has_variant_default())
+            .and_then(|default| comparable_value(default, auto_wrapped));
+
+        for value in self.values.iter() {
+            if redundant_some(&value.value) {
+                warn(
+                    Lint::RedundantSome,
+                    value.get_span(),
+                    some_message.to_owned(),
+                );
+            }
+
+            if default.is_some()
+                && value.self_references.is_none()
+                && comparable_value(&value.value, auto_wrapped) == default
+            {
+                warn(
+                    Lint::RedundantValue,
+                    value.get_span(),
+                    format!("This is the default value of `{}`, it can be removed.", self.ident),
+                );
+            }
+        }
+
+        // the fields of a type aren't alike the way its variants are
+        let set_everywhere = self.values.len() == all_variants.len();
+        if !self.config.is_field()
+            && self.default.is_none()
+            && all_variants.len() > 1
+            && set_everywhere
+        {
+            let values = self
+                .values
+                .iter()
+                .map(|value| match value.self_references {
+                    Some(_) => None,
+                    None => comparable_value(&value.value, auto_wrapped),
+                })
+                .collect::<Option<Vec<_>>>();
+
+            if let Some(values) = values.filter(|values| values.iter().all(|v| v == &values[0])) {
+                warn(
+                    Lint::UniformValue,
+                    SpanRange::from_tokens(&self.ident),
+                    format!(
+                        "Every variant sets `{}` to the same value, \
+                        it could be declared as its default value : `{}: {} = {}`.",
+                        self.ident,
+                        self.ident,
+                        type_string(&self.type_),
+                        values[0].to_token_stream(),
+                    ),
+                );
+            }
+        }
+
+        quote!(#(#warnings)*)
+    }
+}
+
+/// Returns the value to compare with other values, or `None` if its meaning depends on the
+/// variant because it references it or an other attribute.
+fn comparable_value(value: &Expr, auto_wrapped: bool) -> Option<&Expr> {
+    // `Some(x)` and `x` are the same value when the `Some` is added automatically
+    let value = match extract_option_call_arg(value) {
+        Some(inner) if auto_wrapped => inner,
+        _ => value,
+    };
+
+    if has_placeholders(value.to_token_stream()) {
+        None
+    } else {
+        Some(value)
+    }
+}
@@ -0,0 +1,138 @@
+use proc_macro2::{Delimiter, Group, Punct, Spacing, Span, TokenStream, TokenTree};
+use proc_macro_error::{emit_error, SpanRange};
+use quote::ToTokens;
+use syn::{ext::IdentExt, Ident, Variant};
+
+use super::{emit_unknown_attribute, Attribute, FieldAttributes};
+
+impl Attribute {
+    /// Whether the variant opts out of the attribute, so it has no value.
+    pub(super) fn skips(&self, variant: &Ident) -> bool {
+        self.skipped.contains(variant)
+    }
+
+    /// Records that a variant opts out of the attribute with `<attribute> = !`.
+    fn opt_out(&mut self, variant: &Ident, span: Span) {
+        if !self.required {
+            emit_error!(
+                span, "`{}` is optional, it can't be skipped.", self.ident;
+                note = "Optional attributes are `None` on the variants which don't set them."
+            );
+        } else if self.is_set(variant) {
+            emit_error!(span, "`{}` is set on `{}`, it can't be skipped.", self.ident, variant);
+        } else if !self.skips(variant) {
+            self.skipped.push(variant.to_owned());
+        }
+    }
+}
+
+/// The attributes a variant opts out of, with `#[attr(skip)]` or `#[attr(<attribute> = !)]`.
+#[derive(Default)]
+pub(super) struct OptOuts {
+    skip: Option<Ident>,
+    attributes: Vec<Ident>,
+}
+
+/// Splits the content of an `#[attr(...)]` on its top level commas.
+fn split_elements(tokens: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut elements = vec![Vec::new()];
+
+    for tt in tokens {
+        match &tt {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => elements.push(Vec::new()),
+            _ => elements.last_mut().unwrap().push(tt),
+        }
+    }
+
+    elements.into_iter().filter(|e| !e.is_empty()).collect()
+}
+
+/// Removes the opt-outs from the `#[attr(...)]` of a variant, as they aren't values.
+pub(super) fn extract_opt_outs(variant: &mut Variant) -> OptOuts {
+    let mut opt_outs = OptOuts::default();
+
+    for attr in variant.attrs.iter_mut().filter(|a| a.path.is_ident("attr")) {
+        let group = match syn::parse2::<Group>(attr.tokens.to_owned()) {
+            Ok(group) if group.delimiter() == Delimiter::Parenthesis => group,
+            _ => continue,
+        };
+
+        let elements = split_elements(group.stream());
+        let count = elements.len();
+
+        let mut kept = Vec::new();
+        for element in elements {
+            match element.as_slice() {
+                [TokenTree::Ident(ident)] if ident == "skip" => match &opt_outs.skip {
+                    Some(skip) => error_duplicate!(
+                        ident, "The variant already opts out of its attributes.";
+                        SpanRange::from_tokens(skip), "`skip` is already set here."
+                    ),
+                    None => opt_outs.skip = Some(ident.to_owned()),
+                },
+                [TokenTree::Ident(ident), TokenTree::Punct(equal), TokenTree::Punct(not)]
+                    if equal.as_char() == '=' && not.as_char() == '!' =>
+                {
+                    opt_outs.attributes.push(ident.to_owned())
+                }
+                _ => kept.push(element),
+            }
+        }
+
+        // the tokens are kept as is when there is no opt-out, to keep the spans of their errors
+        if kept.len() == count {
+            continue;
+        }
+
+        let mut tokens = TokenStream::new();
+        for (i, element) in kept.into_iter().enumerate() {
+            if i > 0 {
+                tokens.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+            }
+            tokens.extend(element);
+        }
+
+        let mut new_group = Group::new(Delimiter::Parenthesis, tokens);
+        new_group.set_span(group.span());
+        attr.tokens = new_group.into_token_stream();
+    }
+
+    opt_outs
+}
+
+/// Applies the opt-outs of a variant, once its values are set.
+pub(super) fn apply_opt_outs(
+    attributes: &mut [Attribute],
+    field_attributes: &FieldAttributes,
+    variant: &Variant,
+    opt_outs: OptOuts,
+) {
+    for ident in opt_outs.attributes {
+        match attributes.iter_mut().find(|attr| attr.ident == ident) {
+            Some(attr) => attr.opt_out(&variant.ident, ident.span()),
+            None if field_attributes.contains(&ident) => emit_error!(
+                ident,
+                "`{}` is a field attribute, it can only be set on fields.",
+                ident
+            ),
+            None => emit_unknown_attribute(
+                SpanRange::from_tokens(&ident),
+                &ident.unraw().to_string(),
+                attributes,
+            ),
+        }
+    }
+
+    if opt_outs.skip.is_some() {
+        for attr in attributes.iter_mut() {
+            // the default value already covers the variant
+            if attr.required
+                && attr.default.is_none()
+                && !attr.is_set(&variant.ident)
+                && !attr.skips(&variant.ident)
+            {
+                attr.skipped.push(variant.ident.to_owned());
+            }
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error::{emit_error, SpanRange};
+use quote::{quote, ToTokens};
+use syn::{
+    ext::IdentExt, parse_quote, punctuated::Punctuated, spanned::Spanned, token::Comma, Expr,
+    Ident, Variant,
+};
+
+use crate::reference::{substitute_attribute_references, Reference};
+
+use super::{emit_unknown_attribute, Attribute, AttributeMetadata};
+
+/// The value of an attribute for a variant, with its `#attr` references resolved.
+#[derive(Clone)]
+struct ResolvedValue {
+    tokens: TokenStream,
+    self_references: Vec<Reference>,
+}
+
+enum ResolutionState {
+    NotVisited,
+    InProgress,
+    Done(Option<ResolvedValue>),
+}
+
+struct Resolver<'a> {
+    attributes: &'a [Attribute],
+    all_variants: &'a Punctuated<Variant, Comma>,
+    variant: &'a Variant,
+    states: Vec<ResolutionState>,
+    stack: Vec<usize>,
+    reported_cycles: &'a mut Vec<String>,
+}
+
+impl<'a> Resolver<'a> {
+    /// Returns the value of the attribute for the variant, before its references are resolved.
+    fn source(&self, index: usize) -> Option<(Expr, Vec<Reference>)> {
+        let attr = &self.attributes[index];
+        if attr.skips(&self.variant.ident) {
+            return None;
+        }
+
+        let self_references = |metadata: Option<&AttributeMetadata>| {
+            metadata
+                .map(|m| m.references.to_owned())
+                .unwrap_or_default()
+        };
+
+        match attr.values.iter().find(|v| v.variant == self.variant.ident) {
+            Some(value) => Some((
+                value.value.to_owned(),
+                self_references(value.self_references.as_ref()),
+            )),
+            None => match attr.variant_default(self.all_variants, self.variant) {
+                Some(default) => Some((
+                    default,
+                    self_references(attr.default_references.as_ref()),
+                )),
+                None if !attr.required => Some((parse_quote!(None), Vec::new())),
+                // the missing value is reported by the validation
+                None => None,
+            },
+        }
+    }
+
+    fn report_cycle(&mut self, index: usize, span: Span) {
+        let start = self.stack.iter().position(|i| *i == index).unwrap();
+        let cycle = self.stack[start..]
+            .iter()
+            .chain(std::iter::once(&index))
+            .map(|i| format!("`{}`", self.attributes[*i].ident))
+            .collect::<Vec<_>>()
+            .join(" -> ");
+
+        // the same cycle is found for each variant
+        if !self.reported_cycles.contains(&cycle) {
+            emit_error!(span, "Cycle in the attribute references : {}.", cycle);
+            self.reported_cycles.push(cycle);
+        }
+    }
+
+    fn resolve(&mut self, index: usize, span: Span) -> Option<ResolvedValue> {
+        match &self.states[index] {
+            ResolutionState::Done(value) => return value.to_owned(),
+            ResolutionState::InProgress => {
+                self.report_cycle(index, span);
+                return None;
+            }
+            ResolutionState::NotVisited => (),
+        }
+
+        self.states[index] = ResolutionState::InProgress;
+        self.stack.push(index);
+
+        let resolved = self
+            .source(index)
+            .and_then(|(expr, self_references)| self.substitute(expr, self_references));
+
+        self.stack.pop();
+        self.states[index] = ResolutionState::Done(resolved.to_owned());
+
+        resolved
+    }
+
+    fn substitute(
+        &mut self,
+        expr: Expr,
+        mut self_references: Vec<Reference>,
+    ) -> Option<ResolvedValue> {
+        let mut failed = false;
+
+        let tokens = substitute_attribute_references(expr.to_token_stream(), &mut |name, span| {
+            // the unknown attributes are reported by `check_attribute_references_names`
+            let other = match self.attributes.iter().position(|a| a.ident.unraw() == name) {
+                Some(other) => other,
+                None => {
+                    failed = true;
+                    return quote!();
+                }
+            };
+
+            if self.attributes[other].skips(&self.variant.ident) {
+                emit_error!(
+                    span,
+                    "`{}` opts out of `{}`, it can't be referenced.", self.variant.ident, name
+                );
+                failed = true;
+                return quote!();
+            }
+
+            let value = match self.resolve(other, span) {
+                Some(value) => value,
+                None => {
+                    failed = true;
+                    return quote!();
+                }
+            };
+
+            for reference in value.self_references {
+                if !self_references.iter().any(|r| r.name() == reference.name()) {
+                    self_references.push(reference);
+                }
+            }
+
+            let value = self.attributes[other].wrap_value(value.tokens);
+            quote!((#value))
+        });
+
+        if failed {
+            return None;
+        }
+
+        Some(ResolvedValue {
+            tokens,
+            self_references,
+        })
+    }
+}
+
+fn check_attribute_references_names(attributes: &[Attribute], references: &[Reference]) {
+    for reference in references {
+        if !attributes.iter().any(|a| a.ident.unraw() == reference.name()) {
+            for span in reference.spans() {
+                emit_unknown_attribute(SpanRange::single_span(*span), reference.name(), attributes);
+            }
+        }
+    }
+}
+
+/// Replaces the `#attr` references by the value of the referenced attribute for the same
+/// variant, which may itself reference other attributes.
+pub(super) fn resolve_attribute_references(
+    attributes: &mut [Attribute],
+    all_variants: &Punctuated<Variant, Comma>,
+) {
+    for attr in attributes.iter() {
+        check_attribute_references_names(attributes, &attr.default_attribute_references);
+
+        for value in attr.values.iter() {
+            let mut references = Vec::new();
+            substitute_attribute_references(value.value.to_token_stream(), &mut |name, span| {
+                references.push(Reference::new(name.to_owned(), span));
+                quote!()
+            });
+
+            check_attribute_references_names(attributes, &references);
+        }
+    }
+
+    let mut reported_cycles = Vec::new();
+    for variant in all_variants {
+        let mut resolver = Resolver {
+            attributes,
+            all_variants,
+            variant,
+            states: attributes
+                .iter()
+                .map(|_| ResolutionState::NotVisited)
+                .collect(),
+            stack: Vec::new(),
+            reported_cycles: &mut reported_cycles,
+        };
+
+        let resolved = (0..attributes.len())
+            .map(|i| resolver.resolve(i, variant.ident.span()))
+            .collect::<Vec<_>>();
+
+        for (attr, resolved) in attributes.iter_mut().zip(resolved) {
+            let resolved = unwrap_opt_or_continue!(resolved);
+            let expr = match syn::parse2::<Expr>(resolved.tokens) {
+                Ok(expr) => expr,
+                Err(e) => {
+                    emit_error!(
+                        e.span(), "The value of `{}` isn't a valid expression for `{}` once its references are replaced : {}", attr.ident, variant.ident, e;
+                        note = "The `#attr` references are replaced by the value of the attribute, in parentheses."
+                    );
+                    continue;
+                }
+            };
+
+            match attr.values.iter_mut().find(|v| v.variant == variant.ident) {
+                Some(value) => {
+                    let span = value.get_span();
+                    value.value = expr;
+                    value.self_references =
+                        attr_metadata(&attr.ident, resolved.self_references, span);
+                }
+                None if !attr.default_attribute_references.is_empty() => {
+                    let span = match &attr.default_references {
+                        Some(metadata) => metadata.value_real_span,
+                        None => SpanRange::single_span(expr.span()),
+                    };
+                    let metadata = attr_metadata(&attr.ident, resolved.self_references, span);
+
+                    attr.resolved_defaults
+                        .insert(variant.ident.to_owned(), (expr, metadata));
+                }
+                None => (),
+            }
+        }
+    }
+}
+
+fn attr_metadata(
+    ident: &Ident,
+    references: Vec<Reference>,
+    span: SpanRange,
+) -> Option<AttributeMetadata> {
+    if references.is_empty() {
+        None
+    } else {
+        Some(AttributeMetadata::new(ident.to_string(), references, span))
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use proc_macro_error::{emit_error, SpanRange};
+use quote::{format_ident, quote};
+use syn::{punctuated::Punctuated, token::Comma, Expr, Fields, Ident, Variant};
+
+use crate::{literal::literal_key, opt::is_option_wrapped};
+
+use super::Attribute;
+
+impl Attribute {
+    fn value_key(&self, value: &Expr) -> Option<String> {
+        let key = literal_key(value)?;
+
+        if self.wraps_values() && !is_option_wrapped(value) {
+            Some(format!("Some({})", key))
+        } else {
+            Some(key)
+        }
+    }
+
+    pub(super) fn check_unique_values<'a>(
+        &self,
+        all_variants: &Punctuated<Variant, Comma>,
+        variants: impl Iterator<Item = &'a Variant>,
+        reason: &str,
+        skip_none: bool,
+    ) {
+        let mut keys = HashMap::<String, (&Ident, SpanRange, bool)>::new();
+
+        for variant in variants.filter(|v| !self.skips(&v.ident)) {
+            let ident = &variant.ident;
+
+            let (key, span, is_default) = match self.values.iter().find(|v| &v.variant == ident) {
+                Some(value) => (self.value_key(&value.value), value.get_span(), false),
+                None => match self.variant_default(all_variants, variant) {
+                    Some(default) => (
+                        self.value_key(&default),
+                        SpanRange::single_span(ident.span()),
+                        true,
+                    ),
+                    None => continue,
+                },
+            };
+
+            let key = unwrap_opt_or_continue!(key);
+            if skip_none && key == "None" {
+                continue;
+            }
+
+            if let Some((ident2, span2, is_default2)) = keys.get(&key) {
+                let note = if *is_default2 {
+                    format!("`{}` takes the default value.", ident2)
+                } else {
+                    format!("The value of `{}` is set here.", ident2)
+                };
+
+                error_duplicate!(
+                    span, "`{}` has the same `{}` as `{}`, {}.", ident, self.ident, ident2, reason;
+                    *span2, "{}", note
+                );
+
+                continue;
+            }
+
+            keys.insert(key, (ident, span, is_default));
+        }
+    }
+
+    pub(super) fn reverse_tokens(&self, all_variants: &Punctuated<Variant, Comma>) -> TokenStream {
+        if !self.config.is_reverse() {
+            return quote!();
+        }
+
+        let function_name = self.reverse_function_name();
+
+        let mut branches = Vec::new();
+        let mut with_fields = Vec::new();
+
+        for variant in all_variants {
+            let ident = &variant.ident;
+
+            if !matches!(variant.fields, Fields::Unit) {
+                with_fields.push(format!("`{}`", ident));
+                continue;
+            }
+
+            if self.skips(ident) {
+                continue;
+            }
+
+            // the values are bound to the type of the attribute, as a conversion alone doesn't
+            // tell the type to compare with
+            let value = match self.values.iter().find(|v| &v.variant == ident) {
+                Some(value) => value.typed_value(&self.type_),
+                None if self.default.is_some() => self.typed_default(
+                    self.variant_default(all_variants, variant),
+                    self.variant_default_references(variant),
+                ),
+                None => continue,
+            };
+
+            branches.push(quote! {
+                if value == #value {
+                    return Some(Self::#ident)
+                }
+            });
+        }
+
+        let vis = &self.vis;
+        let type_ = &self.type_;
+
+        let mut doc = format!(
+            "Returns the variant whose `{}` attribute is equal to `value`, if any.",
+            self.ident
+        );
+        if !with_fields.is_empty() {
+            doc += &format!(
+                "\n\n{} can't be returned, as they carry fields.",
+                with_fields.join(", ")
+            );
+        }
+
+        quote! {
+            #[doc = #doc]
+            #vis fn #function_name(value: #type_) -> Option<Self> {
+                #(#branches)*
+
+                None
+            }
+        }
+    }
+
+    pub(super) fn reverse_function_name(&self) -> Ident {
+        format_ident!("from_{}", self.ident)
+    }
+}
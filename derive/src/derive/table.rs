@@ -0,0 +1,68 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{ext::IdentExt, punctuated::Punctuated, spanned::Spanned, token::Comma, Ident, Variant};
+
+use super::{match_subject, Attribute};
+
+impl Attribute {
+    fn table_name(&self) -> Ident {
+        format_ident!("{}_TABLE", self.ident.unraw().to_string().to_uppercase())
+    }
+
+    pub(super) fn table_tokens(&self, all_variants: &Punctuated<Variant, Comma>) -> TokenStream {
+        let function_name = self.function_name();
+        let table_name = self.table_name();
+
+        let vis = &self.vis;
+        let type_ = &self.type_;
+        let count = all_variants.len();
+
+        let values = all_variants.iter().map(|var| {
+            match self.values.iter().find(|v| v.variant == var.ident) {
+                Some(value) => value.typed_value(type_),
+                None => self.typed_default(
+                    self.variant_default(all_variants, var),
+                    self.variant_default_references(var),
+                ),
+            }
+        });
+        let indexes = all_variants.iter().enumerate().map(|(i, var)| {
+            let ident = &var.ident;
+            quote!(Self::#ident => #i,)
+        });
+
+        let matched = match_subject(all_variants);
+
+        let comment = self.config.comment();
+        let table_doc = format!(
+            "The values of `{}` for each variant, in declaration order.",
+            self.ident
+        );
+        let constness = self.constness();
+        let reverse = self.reverse_tokens(all_variants);
+        let iter = self.iter_tokens(all_variants);
+
+        // the values are copied out of the table, the bound reports a type which isn't `Copy`
+        // on the declaration rather than in the getter
+        let copy = quote_spanned!(type_.span()=> copy::<#type_>);
+
+        quote! {
+            #[doc = #table_doc]
+            #vis const #table_name: [#type_; #count] = [#(#values),*];
+
+            #[doc = #comment]
+            #vis #constness fn #function_name(&self) -> #type_ {
+                const fn copy<T: ::core::marker::Copy>(value: &T) -> T {
+                    *value
+                }
+
+                #copy(&Self::#table_name[match #matched {
+                    #(#indexes)*
+                }])
+            }
+
+            #reverse
+            #iter
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{GenericParam, Generics, Type};
+
+use crate::{
+    lifetime::has_elided_lifetime,
+    opt::{extract_type_from_option, extract_type_path},
+};
+
+const INT_TYPES: [&str; 6] = ["i8", "i16", "i32", "i64", "i128", "isize"];
+const UINT_TYPES: [&str; 6] = ["u8", "u16", "u32", "u64", "u128", "usize"];
+const FLOAT_TYPES: [&str; 2] = ["f32", "f64"];
+const STRING_PATHS: [&str; 3] = ["String", "std::string::String", "alloc::string::String"];
+
+fn path_string(type_: &Type) -> Option<String> {
+    let path = extract_type_path(type_)?;
+
+    let segments = path
+        .segments
+        .iter()
+        .map(|segment| match segment.arguments {
+            syn::PathArguments::None => Some(segment.ident.to_string()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(segments.join("::"))
+}
+
+fn is_str_reference(type_: &Type) -> bool {
+    match type_ {
+        Type::Reference(reference) if reference.mutability.is_none() => {
+            path_string(&reference.elem).as_deref() == Some("str")
+        }
+        _ => false,
+    }
+}
+
+fn is_static_lifetime(tokens: &[TokenTree]) -> bool {
+    match tokens {
+        [TokenTree::Punct(quote), TokenTree::Ident(ident), ..] => {
+            quote.as_char() == '\'' && ident == "static"
+        }
+        _ => false,
+    }
+}
+
+/// Whether a value of this type can be boxed into a `dyn Any`, which requires it to be `'static`.
+///
+/// Generic parameters and elided lifetimes may not be `'static`, so types using them are rejected.
+fn is_static(tokens: TokenStream, generics: &Generics) -> bool {
+    if has_elided_lifetime(tokens.clone()) {
+        return false;
+    }
+
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+
+    for (i, tt) in tokens.iter().enumerate() {
+        match tt {
+            TokenTree::Group(group) if !is_static(group.stream(), generics) => return false,
+            TokenTree::Punct(punct)
+                if punct.as_char() == '\'' && !is_static_lifetime(&tokens[i..]) =>
+            {
+                return false
+            }
+            TokenTree::Ident(ident) => {
+                let is_generic = generics.params.iter().any(|param| match param {
+                    GenericParam::Type(param) => &param.ident == ident,
+                    _ => false,
+                });
+
+                if is_generic || (ident == "Self" && !generics.params.is_empty()) {
+                    return false;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    true
+}
+
+/// Returns an expression converting `value`, of type `type_`, into an `AttrValue`.
+///
+/// Returns `None` if the type is neither a known primitive nor a `'static` type.
+pub fn attr_value_tokens(
+    type_: &Type,
+    value: TokenStream,
+    generics: &Generics,
+) -> Option<TokenStream> {
+    if let Some(inner) = extract_type_from_option(type_) {
+        let inner_value = attr_value_tokens(inner, quote!(value), generics)?;
+
+        return Some(quote! {
+            ::custom_attrs::AttrValue::Option(
+                #value.map(|value| ::std::boxed::Box::new(#inner_value))
+            )
+        });
+    }
+
+    if is_str_reference(type_) {
+        return Some(quote! {
+            ::custom_attrs::AttrValue::Str(::std::borrow::Cow::Borrowed(#value))
+        });
+    }
+
+    let tokens = match path_string(type_).as_deref() {
+        Some(path) if INT_TYPES.contains(&path) => {
+            quote!(::custom_attrs::AttrValue::Int(#value as i128))
+        }
+        Some(path) if UINT_TYPES.contains(&path) => {
+            quote!(::custom_attrs::AttrValue::UInt(#value as u128))
+        }
+        Some(path) if FLOAT_TYPES.contains(&path) => {
+            quote!(::custom_attrs::AttrValue::Float(#value as f64))
+        }
+        Some("bool") => quote!(::custom_attrs::AttrValue::Bool(#value)),
+        Some("char") => quote!(::custom_attrs::AttrValue::Char(#value)),
+        Some(path) if STRING_PATHS.contains(&path) => {
+            quote!(::custom_attrs::AttrValue::Str(::std::borrow::Cow::Owned(#value)))
+        }
+        _ if is_static(type_.to_token_stream(), generics) => {
+            quote!(::custom_attrs::AttrValue::Any(::std::boxed::Box::new(#value)))
+        }
+        _ => return None,
+    };
+
+    Some(tokens)
+}
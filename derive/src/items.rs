@@ -0,0 +1,95 @@
+use proc_macro2::TokenStream;
+use proc_macro_error::{emit_error, SpanRange};
+use syn::{
+    ext::IdentExt,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token::{self, Comma},
+    Ident,
+};
+
+use crate::{derive::error_duplicate, suggest::emit_unknown_name};
+
+/// A `skip(...)` list in the `#[attr(...)]` of a type, naming generated items.
+pub struct SkipList {
+    _skip: Ident,
+    _paren: token::Paren,
+    items: Punctuated<Ident, Comma>,
+}
+
+impl SkipList {
+    /// Whether the input starts with a `skip(...)` list rather than an attribute declaration.
+    pub fn peek(input: ParseStream) -> bool {
+        input.peek(Ident)
+            && input.peek2(token::Paren)
+            && input
+                .fork()
+                .parse::<Ident>()
+                .is_ok_and(|ident| ident == "skip")
+    }
+}
+
+impl Parse for SkipList {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+
+        Ok(Self {
+            _skip: input.parse()?,
+            _paren: parenthesized!(content in input),
+            items: content.parse_terminated(Ident::parse_any)?,
+        })
+    }
+}
+
+/// The items generated next to the getters, like `VARIANTS` or `attr`, which the type skips
+/// because it already defines an item with the same name.
+#[derive(Default)]
+pub struct GeneratedItems {
+    skipped: Vec<Ident>,
+}
+
+impl GeneratedItems {
+    pub fn add(&mut self, list: SkipList) {
+        for item in list.items {
+            match self.skipped.iter().find(|item2| **item2 == item) {
+                Some(item2) => error_duplicate!(
+                    item, "`{}` is already skipped.", item;
+                    SpanRange::from_tokens(item2), "`{}` is skipped here.", item2
+                ),
+                None => self.skipped.push(item),
+            }
+        }
+    }
+
+    /// Checks that the skipped items are among the `generated` ones.
+    pub fn validate(&self, generated: &[String]) {
+        let unknown = self
+            .skipped
+            .iter()
+            .filter(|item| !generated.contains(&item.unraw().to_string()));
+
+        for item in unknown {
+            emit_unknown_name(
+                SpanRange::from_tokens(item),
+                "This item isn't generated for this type.",
+                &item.unraw().to_string(),
+                generated,
+                "Generated items which can be skipped are",
+            );
+        }
+    }
+
+    pub fn generates(&self, name: &str) -> bool {
+        !self.skipped.iter().any(|item| item.unraw() == name)
+    }
+
+    /// Returns the tokens of an item, or nothing if the type skips it.
+    pub fn item_tokens(&self, name: &str, tokens: TokenStream) -> TokenStream {
+        if self.generates(name) {
+            tokens
+        } else {
+            TokenStream::new()
+        }
+    }
+}
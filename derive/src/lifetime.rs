@@ -0,0 +1,45 @@
+use proc_macro2::{TokenStream, TokenTree};
+
+/// The types which hide their lifetime when it is elided, like `Cow<str>`.
+const HIDDEN_LIFETIME_PATHS: [&str; 1] = ["Cow"];
+
+fn starts_with_lifetime(tokens: &[TokenTree]) -> bool {
+    matches!(tokens.first(), Some(TokenTree::Punct(punct)) if punct.as_char() == '\'')
+}
+
+fn is_anonymous_lifetime(tokens: &[TokenTree]) -> bool {
+    match tokens {
+        [TokenTree::Punct(quote), TokenTree::Ident(ident), ..] => {
+            quote.as_char() == '\'' && ident == "_"
+        }
+        _ => false,
+    }
+}
+
+fn starts_with_angle_bracket(tokens: &[TokenTree]) -> bool {
+    matches!(tokens.first(), Some(TokenTree::Punct(punct)) if punct.as_char() == '<')
+}
+
+/// Whether the type has an elided lifetime, like `&str`, `Foo<'_>` or `Cow<str>`.
+///
+/// Only the types of [`HIDDEN_LIFETIME_PATHS`] are known to hide their lifetime, any other type
+/// must write it as `'_`.
+pub fn has_elided_lifetime(tokens: TokenStream) -> bool {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+
+    tokens.iter().enumerate().any(|(i, tt)| match tt {
+        TokenTree::Group(group) => has_elided_lifetime(group.stream()),
+        TokenTree::Punct(punct) => match punct.as_char() {
+            '&' => !starts_with_lifetime(&tokens[i + 1..]),
+            '\'' => is_anonymous_lifetime(&tokens[i..]),
+            _ => false,
+        },
+        TokenTree::Ident(ident) => {
+            let rest = &tokens[i + 1..];
+
+            HIDDEN_LIFETIME_PATHS.iter().any(|path| ident == path)
+                && !(starts_with_angle_bracket(rest) && starts_with_lifetime(&rest[1..]))
+        }
+        TokenTree::Literal(_) => false,
+    })
+}
@@ -0,0 +1,70 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Attribute, Ident};
+
+const LINT_LEVELS: [&str; 5] = ["allow", "expect", "warn", "deny", "forbid"];
+
+/// The kinds of warnings emitted by the derive macro, named after the constant used to emit them.
+#[derive(Clone, Copy)]
+pub enum Lint {
+    /// An optional attribute without default which no variant sets.
+    UnusedAttribute,
+    /// An attribute set to the same value on every variant.
+    UniformValue,
+    /// A value equal to the default value of the attribute.
+    RedundantValue,
+    /// A value of an optional attribute wrapped into `Some`, while it is done automatically.
+    RedundantSome,
+}
+
+impl Lint {
+    fn name(self) -> &'static str {
+        match self {
+            Self::UnusedAttribute => "unused_attribute",
+            Self::UniformValue => "uniform_value",
+            Self::RedundantValue => "redundant_value",
+            Self::RedundantSome => "redundant_some",
+        }
+    }
+}
+
+/// Returns an item producing a warning with `message`, pointing at `span`.
+///
+/// Procedural macros can't emit warnings on stable, so the warning is the one rustc reports
+/// when a deprecated constant is used. It can be silenced with `#[allow(deprecated)]` on the
+/// type, see [`lints_tokens`].
+pub fn warning_tokens(lint: Lint, span: Span, message: &str) -> TokenStream {
+    let name = Ident::new(lint.name(), Span::call_site());
+    let use_ = Ident::new(lint.name(), span);
+
+    quote! {
+        const _: () = {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const #name: () = ();
+            #use_
+        };
+    }
+}
+
+/// Groups the warnings of a type, under the lint level attributes of the type, so they can be
+/// silenced with `#[allow(deprecated)]` next to the derive.
+///
+/// The warnings are only emitted with the `lints` feature, as they would break the builds
+/// denying warnings of code which compiled cleanly before.
+pub fn lints_tokens(type_attrs: &[Attribute], warnings: TokenStream) -> TokenStream {
+    if !cfg!(feature = "lints") || warnings.is_empty() {
+        return TokenStream::new();
+    }
+
+    let levels = type_attrs
+        .iter()
+        .filter(|attr| LINT_LEVELS.iter().any(|level| attr.path.is_ident(level)));
+
+    quote! {
+        #(#levels)*
+        const _: () = {
+            #warnings
+        };
+    }
+}
@@ -0,0 +1,71 @@
+use syn::{Expr, Lit, UnOp};
+
+use crate::opt::{extract_option_call_arg, is_option_wrapped};
+
+/// Returns the key of an integer, with the separators, the leading zeros and the sign of zero
+/// removed.
+fn int_key(negative: bool, digits: &str) -> String {
+    let digits = digits.replace('_', "");
+    let digits = match digits.trim_start_matches('0') {
+        "" => return String::from("0"),
+        digits => digits,
+    };
+
+    if negative {
+        format!("-{}", digits)
+    } else {
+        digits.to_owned()
+    }
+}
+
+/// Returns the key of a float, from its value so `1.0`, `1.00` and `1e0` get the same key.
+fn float_key(negative: bool, digits: &str) -> String {
+    let value = match digits.replace('_', "").parse::<f64>() {
+        Ok(value) => value,
+        Err(_) => return format!("{}{}", if negative { "-" } else { "" }, digits),
+    };
+
+    // `-0.0 == 0.0`
+    if negative && value != 0.0 {
+        format!("{:?}", -value)
+    } else {
+        format!("{:?}", value)
+    }
+}
+
+fn lit_key(lit: &Lit, negative: bool) -> Option<String> {
+    let key = match lit {
+        Lit::Int(int) => format!("int:{}", int_key(negative, int.base10_digits())),
+        Lit::Float(float) => format!("float:{}", float_key(negative, float.base10_digits())),
+        _ if negative => return None,
+        Lit::Str(str) => format!("str:{:?}", str.value()),
+        Lit::ByteStr(str) => format!("bytes:{:?}", str.value()),
+        Lit::Byte(byte) => format!("byte:{}", byte.value()),
+        Lit::Char(char) => format!("char:{:?}", char.value()),
+        Lit::Bool(bool) => format!("bool:{}", bool.value),
+        Lit::Verbatim(_) => return None,
+    };
+
+    Some(key)
+}
+
+/// Returns a key identifying the value of a literal expression, so two literals written
+/// differently but having the same value get the same key.
+///
+/// Non-literal expressions can't be compared at compile time, so they have no key.
+pub fn literal_key(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(lit) => lit_key(&lit.lit, false),
+        Expr::Paren(paren) => literal_key(&paren.expr),
+        Expr::Group(group) => literal_key(&group.expr),
+        Expr::Unary(unary) => match (&unary.op, unary.expr.as_ref()) {
+            (UnOp::Neg(_), Expr::Lit(lit)) => lit_key(&lit.lit, true),
+            _ => None,
+        },
+        _ if is_option_wrapped(expr) => match extract_option_call_arg(expr) {
+            Some(arg) => literal_key(arg).map(|key| format!("Some({})", key)),
+            None => Some(String::from("None")),
+        },
+        _ => None,
+    }
+}
@@ -95,3 +95,10 @@ pub fn is_option_wrapped(expr: &syn::Expr) -> bool {
 
     false
 }
+
+pub fn extract_option_call_arg(expr: &syn::Expr) -> Option<&syn::Expr> {
+    match expr {
+        Expr::Call(call) if is_option_wrapped(expr) => call.args.first(),
+        _ => None,
+    }
+}
@@ -0,0 +1,746 @@
+use proc_macro2::{Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use proc_macro_error::{emit_error, SpanRange};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token::{self, Comma},
+    Fields, LitStr, Variant,
+};
+
+use crate::case::{to_kebab_case, to_lower_camel_case, to_snake_case};
+
+pub struct ReferenceTokens {
+    tokens: TokenStream,
+    last_span: Option<Span>,
+}
+
+impl Parse for ReferenceTokens {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let (mut tokens, add_comma) = get_tokens(&input);
+
+        // trick to have the correct span for the unexpected end error
+        if tokens.is_empty() {
+            let comma = token::Comma {
+                spans: [input.span()],
+            };
+            comma.to_tokens(&mut tokens);
+
+            return Ok(Self {
+                tokens,
+                last_span: None,
+            });
+        }
+
+        let last_span = if add_comma { Some(input.span()) } else { None };
+
+        Ok(Self { tokens, last_span })
+    }
+}
+
+impl ToTokens for ReferenceTokens {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.tokens.to_tokens(tokens);
+
+        // trick to have the correct span for the unexpected end error
+        if let Some(last_span) = self.last_span {
+            let comma = token::Comma { spans: [last_span] };
+            comma.to_tokens(tokens);
+        }
+    }
+}
+
+fn get_tokens(input: &ParseStream) -> (TokenStream, bool) {
+    input
+        .step(|cursor| {
+            let mut tokens = TokenStream::new();
+            let mut add_comma = true;
+
+            let mut cursor = *cursor;
+            while let Some((tt, next)) = cursor.token_tree() {
+                if let TokenTree::Punct(punct) = &tt {
+                    if punct.as_char() == ',' {
+                        add_comma = false;
+                        break;
+                    }
+                }
+
+                tt.to_tokens(&mut tokens);
+
+                cursor = next;
+            }
+
+            Ok(((tokens, add_comma), cursor))
+        })
+        .unwrap()
+}
+
+/// The properties of a variant which can be referenced with `#variant.<property>`.
+pub const VARIANT_PROPERTIES: [&str; 10] = [
+    "name",
+    "index",
+    "discriminant",
+    "snake_name",
+    "kebab_name",
+    "upper_snake_name",
+    "upper_kebab_name",
+    "camel_name",
+    "lower_name",
+    "upper_name",
+];
+
+/// The name of the self reference standing for `#self` alone, which can't be a field name.
+pub const WHOLE_SELF: &str = "self";
+/// The name of the self reference standing for `#fields`, which can't be a field name.
+pub const ALL_FIELDS: &str = "*";
+
+const VARIANT_PLACEHOLDER_PREFIX: &str = "__custom_attrs_variant_";
+const ATTRIBUTE_PLACEHOLDER_PREFIX: &str = "__custom_attrs_attr_";
+
+/// Variant and attribute references can only be resolved once the variant is known, so they
+/// are replaced by a placeholder ident in the meantime.
+fn placeholder(prefix: &str, name: &str, span: Span) -> Ident {
+    Ident::new(&format!("{}{}", prefix, name), span)
+}
+
+fn substitute_placeholders(
+    tokens: TokenStream,
+    prefix: &str,
+    f: &mut dyn FnMut(&str, Span) -> TokenStream,
+) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|tt| match tt {
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(
+                    group.delimiter(),
+                    substitute_placeholders(group.stream(), prefix, f),
+                );
+                new_group.set_span(group.span());
+                new_group.into_token_stream()
+            }
+            TokenTree::Ident(ident) => {
+                let ident_str = ident.to_string();
+                match ident_str.strip_prefix(prefix) {
+                    Some(name) => f(name, ident.span()),
+                    None => ident.into_token_stream(),
+                }
+            }
+            tt => tt.into_token_stream(),
+        })
+        .collect()
+}
+
+/// Whether the tokens still hold a variant or attribute reference, whose value depends on the
+/// variant.
+pub fn has_placeholders(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        TokenTree::Group(group) => has_placeholders(group.stream()),
+        TokenTree::Ident(ident) => {
+            let ident = ident.to_string();
+            ident.starts_with(VARIANT_PLACEHOLDER_PREFIX)
+                || ident.starts_with(ATTRIBUTE_PLACEHOLDER_PREFIX)
+        }
+        _ => false,
+    })
+}
+
+/// Replaces the `#attr` references of the tokens, `f` being called with the name of the
+/// referenced attribute.
+pub fn substitute_attribute_references(
+    tokens: TokenStream,
+    f: &mut dyn FnMut(&str, Span) -> TokenStream,
+) -> TokenStream {
+    substitute_placeholders(tokens, ATTRIBUTE_PLACEHOLDER_PREFIX, f)
+}
+
+/// Returns a tuple of all the fields, as bound by the pattern of the generated getters.
+fn fields_tuple(fields: &Fields) -> TokenStream {
+    let fields = fields.iter().enumerate().map(|(i, field)| match &field.ident {
+        Some(ident) => ident.to_owned(),
+        None => format_ident!("_{}", i),
+    });
+
+    quote!((#(#fields,)*))
+}
+
+/// Replaces the `#fields` references of the tokens by a tuple of the fields.
+pub fn substitute_fields_references(tokens: TokenStream, fields: &Fields) -> TokenStream {
+    let tuple = fields_tuple(fields);
+
+    substitute_placeholders(tokens, VARIANT_PLACEHOLDER_PREFIX, &mut |property, span| {
+        match property {
+            "fields" => tuple.to_owned(),
+            _ => placeholder(VARIANT_PLACEHOLDER_PREFIX, property, span).into_token_stream(),
+        }
+    })
+}
+
+/// The values of the properties of a variant, substituted to the `#variant` references.
+pub struct VariantProperties {
+    ident: Ident,
+    index: usize,
+    discriminant: TokenStream,
+    fields: TokenStream,
+}
+
+impl VariantProperties {
+    pub fn new(variants: &Punctuated<Variant, Comma>, index: usize) -> Self {
+        // the discriminant of a variant is the one of the last explicit discriminant,
+        // plus the number of variants between them
+        let explicit = variants
+            .iter()
+            .take(index + 1)
+            .enumerate()
+            .rev()
+            .find_map(|(i, v)| v.discriminant.as_ref().map(|(_, expr)| (i, expr)));
+
+        let discriminant = match explicit {
+            Some((i, expr)) if i == index => quote!((#expr)),
+            Some((i, expr)) => {
+                let offset = Literal::usize_unsuffixed(index - i);
+                quote!((#expr + #offset))
+            }
+            None => Literal::usize_unsuffixed(index).into_token_stream(),
+        };
+
+        Self {
+            ident: variants[index].ident.to_owned(),
+            index,
+            discriminant,
+            fields: fields_tuple(&variants[index].fields),
+        }
+    }
+
+    fn property(&self, property: &str, span: Span) -> TokenStream {
+        let name = self.ident.unraw().to_string();
+
+        let string = match property {
+            "index" => {
+                let mut index = Literal::usize_unsuffixed(self.index);
+                index.set_span(span);
+                return index.into_token_stream();
+            }
+            "discriminant" => return self.discriminant.to_owned(),
+            "fields" => return self.fields.to_owned(),
+            "name" => name,
+            "snake_name" => to_snake_case(&name),
+            "kebab_name" => to_kebab_case(&name),
+            "upper_snake_name" => to_snake_case(&name).to_uppercase(),
+            "upper_kebab_name" => to_kebab_case(&name).to_uppercase(),
+            "camel_name" => to_lower_camel_case(&name),
+            "lower_name" => name.to_lowercase(),
+            "upper_name" => name.to_uppercase(),
+            _ => unreachable!("unknown variant property"),
+        };
+
+        LitStr::new(&string, span).into_token_stream()
+    }
+
+    /// Replaces the `#variant` references of the tokens by the properties of this variant.
+    pub fn substitute(&self, tokens: TokenStream) -> TokenStream {
+        substitute_placeholders(tokens, VARIANT_PLACEHOLDER_PREFIX, &mut |property, span| {
+            self.property(property, span)
+        })
+    }
+}
+
+pub struct ReferenceProcessor {
+    tokens: TokenStream,
+    references: Vec<ReferenceList>,
+    span: SpanRange,
+}
+
+impl ReferenceProcessor {
+    pub fn into_parts(self) -> (TokenStream, Vec<ReferenceList>, SpanRange) {
+        (self.tokens, self.references, self.span)
+    }
+
+    pub fn parse(tokens: TokenStream) -> Result<Self, ()> {
+        let span = SpanRange::from_tokens(&tokens);
+
+        let mut references = Vec::new();
+        let (new_tokens, errored) = parse_references(tokens, &mut references, false);
+        if errored {
+            return Err(());
+        }
+
+        Ok(Self {
+            tokens: new_tokens,
+            references,
+            span,
+        })
+    }
+}
+
+impl ToTokens for ReferenceProcessor {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.tokens.to_tokens(tokens);
+    }
+}
+
+pub struct ReferenceList {
+    name: Reference,
+    list: Vec<Reference>,
+}
+
+impl ReferenceList {
+    pub fn name(&self) -> &Reference {
+        &self.name
+    }
+
+    pub fn into_list(self) -> Vec<Reference> {
+        self.list
+    }
+
+    fn new(name: Reference) -> Self {
+        Self {
+            name,
+            list: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, name: String, span: Span) {
+        match self.list.iter_mut().find(|r| r.name == name) {
+            Some(reference) => reference.spans.push(span),
+            None => self.list.push(Reference::new(name, span)),
+        }
+    }
+}
+
+/// Returns the index of the list of the self references, creating it if needed.
+fn self_reference_list(references: &mut Vec<ReferenceList>, span: Span) -> usize {
+    match references.iter().position(|r| r.name.name == "self") {
+        Some(index) => index,
+        None => {
+            references.push(ReferenceList::new(Reference::new("self".to_owned(), span)));
+            references.len() - 1
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Reference {
+    name: String,
+    spans: Vec<Span>,
+}
+
+impl Reference {
+    pub fn new(name: String, span: Span) -> Self {
+        Self {
+            name,
+            spans: vec![span],
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    pub fn emit_error(&self, msg: &str) {
+        for span in &self.spans {
+            emit_error!(span, msg);
+        }
+    }
+}
+
+/// Splits a tuple index, which is a float literal when two indexes are chained like in `0.1`.
+fn split_tuple_index(lit: &Literal) -> Option<(String, Option<usize>)> {
+    match syn::parse2(lit.to_token_stream()).ok()? {
+        syn::Lit::Int(int) if int.suffix().is_empty() => Some((int.to_string(), None)),
+        syn::Lit::Float(float) if float.suffix().is_empty() => {
+            let (index, nested) = float.base10_digits().split_once('.')?;
+            let is_index = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+
+            if !is_index(index) || !is_index(nested) {
+                return None;
+            }
+
+            Some((index.to_owned(), Some(nested.parse().ok()?)))
+        }
+        _ => None,
+    }
+}
+
+enum State {
+    None,
+    ExpectingReference,
+    ExpectingPoint,
+    ExpectingIdent,
+    /// The path following an unknown reference, which is already reported.
+    SkippingPath { expecting_point: bool },
+}
+
+fn is_macro_call(previous: &[TokenTree]) -> bool {
+    match previous {
+        [.., TokenTree::Ident(_), TokenTree::Punct(bang)] => bang.as_char() == '!',
+        _ => false,
+    }
+}
+
+fn parse_group(
+    group: &Group,
+    references: &mut Vec<ReferenceList>,
+    in_macro: bool,
+) -> (Group, bool) {
+    let (tokens, errored) = parse_references(group.stream(), references, in_macro);
+
+    let mut new_group = Group::new(group.delimiter(), tokens);
+    new_group.set_span(group.span());
+
+    (new_group, errored)
+}
+
+/// Replaces the references of the tokens by the tokens they stand for.
+///
+/// `##` is an escaped `#`. Inside the body of a macro invocation, where `#` may be part of the
+/// macro syntax, a `#` which doesn't start a known reference is kept as is.
+fn parse_references(
+    tokens: TokenStream,
+    references: &mut Vec<ReferenceList>,
+    in_macro: bool,
+) -> (TokenStream, bool) {
+    let mut new_tokens = TokenStream::new();
+    let mut state = State::None;
+    let mut previous = Vec::new();
+
+    let mut last_reference = String::new();
+    let mut last_reference_index = 0_usize;
+    let mut last_reference_span = Span::call_site();
+
+    let mut last_span = tokens.span();
+
+    let mut errored = false;
+
+    for tt in tokens {
+        last_span = tt.span();
+
+        let macro_body = in_macro || is_macro_call(&previous);
+        previous.push(tt.clone());
+
+        match state {
+            State::None => {
+                if let TokenTree::Group(group) = &tt {
+                    let (new_group, errored2) = parse_group(group, references, macro_body);
+                    new_group.to_tokens(&mut new_tokens);
+
+                    errored = errored || errored2;
+                    continue;
+                }
+
+                if let TokenTree::Punct(punct) = &tt {
+                    if punct.as_char() == '#' {
+                        state = State::ExpectingReference;
+                        continue;
+                    }
+                }
+            }
+            State::ExpectingReference => {
+                let pound = Punct::new('#', Spacing::Alone);
+
+                // pound are also used in attributes
+                if let TokenTree::Group(group) = &tt {
+                    let (new_group, errored2) = parse_group(group, references, macro_body);
+                    pound.to_tokens(&mut new_tokens);
+                    new_group.to_tokens(&mut new_tokens);
+
+                    errored = errored || errored2;
+                    state = State::None;
+                    continue;
+                }
+
+                let is_reference = |ident: &Ident| {
+                    ["self", "variant", "attr", "fields"].contains(&ident.to_string().as_str())
+                };
+                let pass_through = match &tt {
+                    // escaped pound
+                    TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                        pound.to_tokens(&mut new_tokens);
+                        state = State::None;
+                        continue;
+                    }
+                    TokenTree::Ident(ident) => in_macro && !is_reference(ident),
+                    _ => in_macro,
+                };
+
+                if pass_through {
+                    pound.to_tokens(&mut new_tokens);
+                    tt.to_tokens(&mut new_tokens);
+                    state = State::None;
+                    continue;
+                }
+
+                if let TokenTree::Ident(ident) = &tt {
+                    let ident_str = ident.to_string();
+                    match ident_str.as_str() {
+                        "fields" => {
+                            let index = self_reference_list(references, ident.span());
+                            references[index].add(ALL_FIELDS.to_owned(), ident.span());
+
+                            placeholder(VARIANT_PLACEHOLDER_PREFIX, "fields", ident.span())
+                                .to_tokens(&mut new_tokens);
+
+                            state = State::None;
+                            continue;
+                        }
+                        "self" | "variant" | "attr" => {
+                            last_reference = ident_str.to_owned();
+                            last_reference_span = ident.span();
+
+                            let match_ = references
+                                .iter()
+                                .enumerate()
+                                .find(|(_i, r)| r.name.name == ident_str);
+
+                            if let Some((i, _)) = match_ {
+                                last_reference_index = i;
+                            } else {
+                                last_reference_index = references.len();
+                                references.push(ReferenceList::new(Reference::new(
+                                    ident_str,
+                                    ident.span(),
+                                )));
+                            }
+                        }
+                        _ => {
+                            emit_error!(ident, "Unknown reference.");
+                            errored = true;
+
+                            state = State::SkippingPath {
+                                expecting_point: true,
+                            };
+                            continue;
+                        }
+                    }
+
+                    state = State::ExpectingPoint;
+                    continue;
+                }
+
+                emit_error!(tt.span(), "Expecting reference ident or `[`.");
+                state = State::None;
+                errored = true;
+                continue;
+            }
+            State::ExpectingPoint => {
+                if let TokenTree::Punct(punct) = &tt {
+                    if punct.as_char() == '.' {
+                        state = State::ExpectingIdent;
+                        continue;
+                    }
+                }
+
+                // `#self` alone is the whole variant
+                if last_reference == "self" {
+                    references[last_reference_index]
+                        .add(WHOLE_SELF.to_owned(), last_reference_span);
+                    Ident::new("self", last_reference_span).to_tokens(&mut new_tokens);
+
+                    state = State::None;
+                    match &tt {
+                        TokenTree::Group(group) => {
+                            let (new_group, errored2) = parse_group(group, references, macro_body);
+                            new_group.to_tokens(&mut new_tokens);
+                            errored = errored || errored2;
+                        }
+                        TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                            state = State::ExpectingReference;
+                        }
+                        _ => tt.to_tokens(&mut new_tokens),
+                    }
+                    continue;
+                }
+
+                emit_error!(tt.span(), "Expecting `.`.");
+                state = State::None;
+                errored = true;
+                continue;
+            }
+            State::ExpectingIdent if last_reference == "variant" => {
+                let ident = match &tt {
+                    TokenTree::Ident(ident) => ident,
+                    _ => {
+                        emit_error!(tt.span(), "Expecting variant property.");
+                        errored = true;
+                        state = State::None;
+                        continue;
+                    }
+                };
+
+                let ident_str = ident.to_string();
+                if !VARIANT_PROPERTIES.contains(&ident_str.as_str()) {
+                    emit_error!(
+                        ident, "Unknown variant property.";
+                        help = "Available properties are {}.", VARIANT_PROPERTIES.map(|p| format!("`{}`", p)).join(", ")
+                    );
+                    errored = true;
+                    state = State::None;
+                    continue;
+                }
+
+                placeholder(VARIANT_PLACEHOLDER_PREFIX, &ident_str, ident.span())
+                    .to_tokens(&mut new_tokens);
+
+                let list = &mut references[last_reference_index].list;
+                match list.iter_mut().find(|r| r.name == ident_str) {
+                    Some(ref_) => ref_.spans.push(ident.span()),
+                    None => list.push(Reference::new(ident_str, ident.span())),
+                }
+
+                state = State::None;
+                continue;
+            }
+            State::ExpectingIdent if last_reference == "attr" => {
+                let ident = match &tt {
+                    TokenTree::Ident(ident) => ident,
+                    _ => {
+                        emit_error!(tt.span(), "Expecting attribute ident.");
+                        errored = true;
+                        state = State::None;
+                        continue;
+                    }
+                };
+
+                let ident_str = ident.unraw().to_string();
+                placeholder(ATTRIBUTE_PLACEHOLDER_PREFIX, &ident_str, ident.span())
+                    .to_tokens(&mut new_tokens);
+
+                let list = &mut references[last_reference_index].list;
+                match list.iter_mut().find(|r| r.name == ident_str) {
+                    Some(ref_) => ref_.spans.push(ident.span()),
+                    None => list.push(Reference::new(ident_str, ident.span())),
+                }
+
+                state = State::None;
+                continue;
+            }
+            State::SkippingPath { expecting_point } => {
+                let in_path = match &tt {
+                    TokenTree::Punct(punct) => expecting_point && punct.as_char() == '.',
+                    TokenTree::Ident(_) | TokenTree::Literal(_) => !expecting_point,
+                    TokenTree::Group(_) => false,
+                };
+
+                if in_path {
+                    state = State::SkippingPath {
+                        expecting_point: !expecting_point,
+                    };
+                    continue;
+                }
+
+                state = State::None;
+                match &tt {
+                    TokenTree::Group(group) => {
+                        let (new_group, errored2) = parse_group(group, references, macro_body);
+                        new_group.to_tokens(&mut new_tokens);
+                        errored = errored || errored2;
+                        continue;
+                    }
+                    TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                        state = State::ExpectingReference;
+                        continue;
+                    }
+                    _ => (),
+                }
+            }
+            State::ExpectingIdent => {
+                let (ident_str, span) = match &tt {
+                    TokenTree::Ident(ident) => {
+                        ident.to_tokens(&mut new_tokens);
+
+                        (ident.to_string(), ident.span())
+                    }
+                    TokenTree::Literal(lit) => {
+                        let (lit_str, nested) = match split_tuple_index(lit) {
+                            Some(index) => index,
+                            None => {
+                                emit_error!(tt.span(), "Expecting ident or integer.");
+                                state = State::None;
+                                errored = true;
+                                continue;
+                            }
+                        };
+
+                        let field_str = format!("_{0}", lit_str);
+                        let field = Ident::new(&field_str, lit.span());
+
+                        field.to_tokens(&mut new_tokens);
+
+                        // only the top-level field is bound, the nested one is a field access
+                        if let Some(nested) = nested {
+                            let mut nested = Literal::usize_unsuffixed(nested);
+                            nested.set_span(lit.span());
+
+                            Punct::new('.', Spacing::Alone).to_tokens(&mut new_tokens);
+                            nested.to_tokens(&mut new_tokens);
+                        }
+
+                        (lit_str, field.span())
+                    }
+                    _ => {
+                        emit_error!(tt.span(), "Expecting ident.");
+                        errored = true;
+                        state = State::None;
+                        continue;
+                    }
+                };
+
+                let list = &mut references[last_reference_index].list;
+                let match_ = list.iter_mut().find(|r| r.name == ident_str.as_str());
+
+                if let Some(ref_) = match_ {
+                    ref_.spans.push(span)
+                } else {
+                    list.push(Reference::new(ident_str, span))
+                }
+
+                state = State::None;
+                continue;
+            }
+        }
+
+        tt.to_tokens(&mut new_tokens);
+    }
+
+    match state {
+        State::None | State::SkippingPath { .. } => (),
+        State::ExpectingReference if in_macro => {
+            Punct::new('#', Spacing::Alone).to_tokens(&mut new_tokens);
+        }
+        State::ExpectingReference => {
+            emit_error!(last_span, "Expecting reference ident or `[`.");
+            errored = true;
+        }
+        State::ExpectingPoint if last_reference == "self" => {
+            references[last_reference_index].add(WHOLE_SELF.to_owned(), last_reference_span);
+            Ident::new("self", last_reference_span).to_tokens(&mut new_tokens);
+        }
+        State::ExpectingPoint => {
+            emit_error!(last_span, "Expecting `.`.");
+            errored = true;
+        }
+        State::ExpectingIdent => {
+            match last_reference.as_str() {
+                "self" => {
+                    emit_error!(last_span, "Expecting field ident.");
+                }
+                "variant" => {
+                    emit_error!(last_span, "Expecting variant property.");
+                }
+                "attr" => {
+                    emit_error!(last_span, "Expecting attribute ident.");
+                }
+                _ => panic!("Unknown reference."),
+            }
+            errored = true;
+        }
+    }
+
+    (new_tokens, errored)
+}
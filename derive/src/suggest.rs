@@ -0,0 +1,92 @@
+use proc_macro_error::{emit_error, SpanRange};
+
+/// Returns the number of single character edits needed to turn `a` into `b`, a swap of two
+/// adjacent characters counting as one edit, as it is the most common typo.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    // `distances[i][j]` is the distance between the first `i` characters of `a` and the first
+    // `j` characters of `b`
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = substitution
+                .min(distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+/// Returns the candidate closest to `name`, if it is close enough to be a typo of it.
+///
+/// A candidate must keep some of its characters, otherwise any one letter name would be a typo
+/// of any other.
+pub fn similar_name<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, candidate)| {
+            *distance <= max_distance && *distance < candidate.chars().count()
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Formats a list of names as "`a`, `b` and `c`".
+pub fn name_list<S: AsRef<str>>(names: &[S]) -> String {
+    let names = names
+        .iter()
+        .map(|name| format!("`{}`", name.as_ref()))
+        .collect::<Vec<_>>();
+
+    match names.split_last() {
+        Some((last, [])) => last.to_owned(),
+        Some((last, others)) => format!("{} and {}", others.join(", "), last),
+        None => String::new(),
+    }
+}
+
+/// Emits an error for an unknown name, suggesting the closest candidate, or listing all of them
+/// after `list_prefix` when none is close.
+pub fn emit_unknown_name<S: AsRef<str>>(
+    span: SpanRange,
+    message: &str,
+    name: &str,
+    candidates: &[S],
+    list_prefix: &str,
+) {
+    let similar = similar_name(name, candidates.iter().map(AsRef::as_ref));
+
+    let help = similar.map(|similar| format!("Did you mean `{}` ?", similar));
+    let note = match similar {
+        None if !candidates.is_empty() => {
+            Some(format!("{} {}.", list_prefix, name_list(candidates)))
+        }
+        _ => None,
+    };
+
+    emit_error!(
+        span, message;
+        help =? help;
+        note =? note
+    );
+}
@@ -82,7 +82,7 @@ impl<I: ToTokens, V: ToTokens> ToTokens for IdentOptionalValueAssignmentGeneric<
     }
 }
 
-pub type ValueAssignment = ValueAssignmentGeneric<Expr>;
+pub type ValueAssignment = ValueAssignmentGeneric<ReferenceTokens>;
 
 pub struct ValueAssignmentGeneric<V> {
     _equal: Token!(=),
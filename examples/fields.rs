@@ -0,0 +1,28 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[field]
+    pub label: &'static str
+)]
+#[attr(
+    #[field]
+    pub sensitive: bool = false
+)]
+#[allow(unused)]
+struct Form {
+    #[attr(label = "Name")]
+    name: String,
+
+    #[attr(label = "Password", sensitive = true)]
+    password: String,
+}
+
+fn main() {
+    for (name, field) in Form::field_attrs() {
+        let _label = field.get_label(); // getter on the companion enum
+        let _sensitive = Form::field_sensitive(field); // getter on the type
+
+        let _ = name;
+    }
+}
@@ -2,16 +2,19 @@
 use custom_attrs::CustomAttrs;
 
 #[derive(CustomAttrs)]
-#[attr(pub a: Option<usize> = Some(3))]
+#[attr(pub a: Option<usize>)]
+#[attr(pub b: Option<usize> = 3)]
 enum Enum {
     Variant1,
 
-    #[attr(a = 3)]
+    #[attr(a = 3, b = None)]
     Variant2,
 }
 
 fn main() {
-    let a = Enum::Variant1.get_a();
+    assert!(Enum::Variant1.get_a().is_none());
+    assert_eq!(Enum::Variant1.get_b(), Some(3));
 
-    assert!(a.is_none());
+    assert_eq!(Enum::Variant2.get_a(), Some(3));
+    assert!(Enum::Variant2.get_b().is_none());
 }
@@ -0,0 +1,22 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    /// Name of the table where the records are stored.
+    pub table: &'static str = "records"
+)]
+#[attr(pub schema_version: u32 = 2)]
+#[attr(pub id_len: usize = #self.id.len())]
+struct Record {
+    id: String,
+}
+
+fn main() {
+    let record = Record {
+        id: String::from("abcd"),
+    };
+
+    let _table = Record::table(); // associated function
+    let _version = record.get_schema_version(); // getter
+    let _id_len = record.get_id_len(); // getter only, as it uses a self reference
+}
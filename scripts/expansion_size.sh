@@ -0,0 +1,62 @@
+#!/bin/sh
+# Measures the expansion of the derive on the 300 variants enum of `tests/large_enum.rs`: the
+# size of the expanded test, and the time taken to build it.
+#
+# Usage: scripts/expansion_size.sh [revision]
+#
+# With a revision, it is checked out in a temporary git worktree, and the same enum is measured
+# there too, printing the sizes of both trees and their difference. The enum only uses literal
+# values, so it expands on any revision.
+#
+# Fails when the expansion exceeds `MAX_BYTES`, so a change generating much larger code is
+# noticed. `-Zunpretty=expanded` is nightly only, `RUSTC_BOOTSTRAP` enables it on stable.
+
+set -e
+cd "$(dirname "$0")/.."
+
+MAX_BYTES=${MAX_BYTES:-200000}
+
+# Prints the size of the expansion of `tests/large_enum.rs` in the tree of the current
+# directory, and the time taken to build it. Sets `bytes` to the size.
+measure() {
+    expanded=$(RUSTC_BOOTSTRAP=1 cargo rustc --quiet --profile test --test large_enum -- -Zunpretty=expanded)
+    lines=$(printf '%s\n' "$expanded" | wc -l)
+    bytes=$(printf '%s\n' "$expanded" | wc -c)
+
+    # builds the dependencies first, so only the test is timed
+    cargo test --quiet --no-run --test large_enum 2>/dev/null
+    touch tests/large_enum.rs
+    start=$(date +%s%N)
+    cargo test --quiet --no-run --test large_enum 2>/dev/null
+    end=$(date +%s%N)
+
+    echo "expansion: $lines lines, $bytes bytes"
+    echo "build time: $(( (end - start) / 1000000 )) ms"
+}
+
+if [ -n "$1" ]; then
+    worktree=$(mktemp -d)
+    trap 'git worktree remove --force "$worktree"' EXIT
+    git worktree add --quiet --detach "$worktree" "$1"
+    cp tests/large_enum.rs "$worktree/tests/large_enum.rs"
+
+    echo "$1:"
+    cd "$worktree"
+    measure
+    base_bytes=$bytes
+    cd - >/dev/null
+    echo
+fi
+
+echo "working tree:"
+measure
+
+if [ -n "$1" ]; then
+    echo
+    echo "difference: $(( bytes - base_bytes )) bytes"
+fi
+
+if [ "$bytes" -gt "$MAX_BYTES" ]; then
+    echo "The expansion is larger than $MAX_BYTES bytes."
+    exit 1
+fi
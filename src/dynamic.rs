@@ -0,0 +1,87 @@
+use std::{any::Any, borrow::Cow};
+
+/// The value of an attribute, returned by the `attr` function of a type deriving `CustomAttrs`.
+///
+/// Primitive types are converted to the widest type of their kind, so the value can be read
+/// without knowing the exact declared type. Any other type is boxed into `Any`, and can be
+/// recovered with [`AttrValue::downcast`], unless it may not be `'static`.
+#[derive(Debug)]
+pub enum AttrValue<'a> {
+    /// A signed integer.
+    Int(i128),
+    /// An unsigned integer.
+    UInt(u128),
+    /// A floating point number.
+    Float(f64),
+    Bool(bool),
+    Char(char),
+    /// A `&str` or a `String`.
+    Str(Cow<'a, str>),
+    /// An `Option` of one of the other kinds of value.
+    Option(Option<Box<AttrValue<'a>>>),
+    /// A value of any other type.
+    Any(Box<dyn Any>),
+    /// The value of an attribute whose type can't be boxed into `Any`, as it may not be `'static`
+    /// because it uses a generic parameter or a lifetime other than `'static`.
+    Unsupported,
+}
+
+impl<'a> AttrValue<'a> {
+    pub fn as_int(&self) -> Option<i128> {
+        match self {
+            Self::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_uint(&self) -> Option<u128> {
+        match self {
+            Self::UInt(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_char(&self) -> Option<char> {
+        match self {
+            Self::Char(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value inside an `Option` attribute, `None` if it is not set or if the attribute is not an `Option`.
+    pub fn as_option(&self) -> Option<&AttrValue<'a>> {
+        match self {
+            Self::Option(value) => value.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to a value stored in `Any`, if it is of type `T`.
+    pub fn downcast<T: Any>(&self) -> Option<&T> {
+        match self {
+            Self::Any(value) => value.downcast_ref(),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+/// Describes an attribute declared on a type deriving `CustomAttrs`.
+///
+/// The list of the attributes of a type is returned by its `attribute_info` function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AttributeInfo {
+    name: &'static str,
+    getter: &'static str,
+    type_: &'static str,
+    doc: &'static str,
+    optional: bool,
+    has_default: bool,
+}
+
+impl AttributeInfo {
+    #[doc(hidden)]
+    pub const fn __new(
+        name: &'static str,
+        getter: &'static str,
+        type_: &'static str,
+        doc: &'static str,
+        optional: bool,
+        has_default: bool,
+    ) -> Self {
+        Self {
+            name,
+            getter,
+            type_,
+            doc,
+            optional,
+            has_default,
+        }
+    }
+
+    /// The name of the attribute, as written in its declaration.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The name of the getter function of the attribute, which is `try_get_<attribute>` when some
+    /// variants opt out of it.
+    pub const fn getter(&self) -> &'static str {
+        self.getter
+    }
+
+    /// The type of the attribute, as written in its declaration.
+    pub const fn type_name(&self) -> &'static str {
+        self.type_
+    }
+
+    /// The documentation of the attribute, empty if it has none.
+    pub const fn doc(&self) -> &'static str {
+        self.doc
+    }
+
+    /// Whether the type of the attribute is an `Option`, so it doesn't need to be set on every variant.
+    pub const fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    /// Whether the attribute has a default value.
+    pub const fn has_default(&self) -> bool {
+        self.has_default
+    }
+}
@@ -159,12 +159,118 @@
 //! }
 //! ```
 //! 
+//! Fields of tuple fields can be reached with chained indexes, like `#self.0.1`.
+//! 
+//! `#self` alone is the variant itself, and `#fields` is a tuple of references to all the fields of the variant, so the value can be computed by a function :
+//! 
+//! ```rust, ignore
+//! #[attr(weight: usize = weight(#self))]
+//! #[attr(payload: String = format!("{:?}", #fields))]
+//! enum Enum {
+//!     // payload is "(1, 2)"
+//!     Pair(u8, u8),
+//! }
+//! ```
+//! 
 //! If you're using self reference, the value returned will also be a reference. You can deref it like so :
 //! 
 //! ```rust, ignore
 //! #[attr(name = *#self.<field>)]
 //! ```
 //! 
+//! Self references can also be used in the default value of an attribute. They resolve against the fields of each variant taking the default value, so these variants must all have the referenced fields.
+//! 
+//! ```rust, ignore
+//! #[attr(len: usize = #self.0.len())]
+//! enum Enum {
+//!     Name(String),
+//!     List(Vec<u8>),
+//! 
+//!     // no field, so the value must be set
+//!     #[attr(len = 0)]
+//!     Empty,
+//! }
+//! ```
+//! 
+//! #### Variant References
+//! 
+//! Values can also reference properties of the variant they are set on with `#variant.<property>` :
+//! 
+//! - `name` : the name of the variant, as a string literal.
+//! - `snake_name`, `kebab_name`, `upper_snake_name`, `upper_kebab_name`, `camel_name`, `lower_name`, `upper_name` : the name of the variant converted to another case.
+//! - `index` : the position of the variant in the enum.
+//! - `discriminant` : the discriminant of the variant, only on enums with an integer `repr`.
+//! 
+//! In a default value, they resolve to the properties of each variant taking it.
+//! 
+//! ```rust, ignore
+//! #[attr(name: &'static str = #variant.snake_name)]
+//! enum Enum {
+//!     // "http_server"
+//!     HttpServer,
+//! 
+//!     #[attr(name = concat!("x-", #variant.kebab_name))]
+//!     ReadOnly,
+//! }
+//! ```
+//! 
+//! #### Attribute References
+//! 
+//! Values can use the value of another attribute of the same variant with `#attr.<attribute>`. The referenced value is the one returned by its getter, so it is wrapped into `Some` if the attribute is optional.
+//! 
+//! ```rust, ignore
+//! #[attr(name: &'static str)]
+//! #[attr(code: u32)]
+//! #[attr(label: String = format!("{} ({})", #attr.name, #attr.code))]
+//! enum Enum {
+//!     // label is "first (1)"
+//!     #[attr(name = "first", code = 1)]
+//!     First,
+//! }
+//! ```
+//! 
+//! The references are resolved when the macro is expanded, so an attribute can't reference itself, even through other attributes.
+//! The expression of the referenced value is copied in place of the reference, rather than calling its getter, so it is evaluated again each time it is used : a value with side effects, or an expensive one, is better computed once in a function.
+//! 
+//! #### Escaping `#`
+//! 
+//! As `#` starts a reference, a literal `#` is written `##`.
+//! Inside the body of a macro invocation, a `#` which doesn't start a reference is kept as is, so macros using `#` in their syntax, like `quote!`, can be used in values.
+//! 
+//! ```rust, ignore
+//! #[attr(a: TokenStream = quote!(#ident + #self.0))]
+//! ```
+//! 
+//! #### Opting out
+//! 
+//! A variant can opt out of a required attribute with `<attribute> = !`, or of every required attribute without default value it doesn't set with `skip`. Optional attributes are `None` on these variants, as usual, and the attributes with a default value keep it.
+//! 
+//! ```rust, ignore
+//! #[derive(CustomAttrs)]
+//! #[attr(code: u32)]
+//! #[attr(label: &'static str = "unknown")]
+//! enum Command {
+//!     #[attr(code = 1)]
+//!     Open,
+//! 
+//!     #[attr(code = !)]
+//!     Save,
+//! 
+//!     #[attr(skip)]
+//!     Internal,
+//! }
+//! ```
+//! 
+//! The getter of these attributes is then replaced by a `try_get_<attribute>` function, returning a `MissingAttr` error on the variants opting out. `iter_<attribute>` leaves them out.
+//! 
+//! **Opting out renames the getter** : `get_<attribute>` only exists while every variant has a value, so the first variant opting out of an attribute removes it, and its callers have to use `try_get_<attribute>` instead. Opting the last variant back in does the opposite.
+//! 
+//! ```rust, ignore
+//! assert_eq!(Command::Open.try_get_code(), Ok(1));
+//! assert!(Command::Save.try_get_code().is_err());
+//! assert_eq!(Command::Internal.get_label(), "unknown");
+//! ```
+//! 
 //! ### Attribute configuration
 //! 
 //! You can configure you attributes to change their characteristics.
@@ -204,6 +310,52 @@
 //! 
 //! Here is a list of all the properties :
 //! - `function` : defines the name of the function to get the attribute
+//! - `field` : makes the attribute a field attribute
+//! - `reverse` : generates a `from_<attribute>` function returning the variant having a value
+//! - `unique` : requires each variant to have a different value
+//! - `const_fn` : makes the getter a `const fn`
+//! - `table` : stores the values in a constant array
+//! - `optional` : makes the attribute optional, even if its type isn't written as an `Option`, like a type alias
+//! - `required` : requires a value on each variant, even if the type of the attribute is an `Option`
+//! - `no_wrap` : disables the automatic `Some` wrapping of the values of an optional attribute
+//! - `convert` : converts each value with `Into::into`, using `convert = "into"`
+//! - `wrap` : passes each value to a function, like `wrap = Box::new`
+//! 
+//! ```rust, ignore
+//! type MaybeId = Option<u32>;
+//! 
+//! #[derive(CustomAttrs)]
+//! #[attr(
+//!     #[optional]
+//!     id: MaybeId
+//! )]
+//! #[attr(
+//!     #[no_wrap]
+//!     limit: Option<Option<u32>>
+//! )]
+//! enum Enum {
+//!     #[attr(id = 3, limit = Some(None))]
+//!     Variant,
+//! }
+//! ```
+//! 
+//! The conversion of `convert` and `wrap` applies to the values of the variants and to the default value, so they can be written as plain literals. For optional attributes, the value is converted before being wrapped into `Some`.
+//! 
+//! ```rust, ignore
+//! #[derive(CustomAttrs)]
+//! #[attr(
+//!     #[convert = "into"]
+//!     name: String
+//! )]
+//! #[attr(
+//!     #[wrap = Box::new]
+//!     display: Box<dyn Display> = "none"
+//! )]
+//! enum Enum {
+//!     #[attr(name = "variant", display = 3)]
+//!     Variant,
+//! }
+//! ```
 //! 
 //! ### Getting a value attribute
 //! 
@@ -217,6 +369,238 @@
 //! 
 //! If you've set a documentation on the attribute, it will be shown on this function.
 //! 
+//! ### Reverse lookup
+//! 
+//! With the `reverse` config, a `from_<attribute>` function is generated, returning the variant having the value given, if any.
+//! 
+//! ```rust, ignore
+//! #[derive(CustomAttrs)]
+//! #[attr(
+//!     #[reverse]
+//!     pub code: u32
+//! )]
+//! enum Error {
+//!     #[attr(code = 404)]
+//!     NotFound,
+//! 
+//!     #[attr(code = 500)]
+//!     Internal,
+//! }
+//! 
+//! Error::from_code(404); // Some(Error::NotFound)
+//! ```
+//! 
+//! Only unit variants can be returned. If two variants have the same literal value, the library will produce an error.
+//! 
+//! ### Unique values
+//! 
+//! With the `unique` config, the library will produce an error if two variants have the same literal value for the attribute. Numbers are compared by value, so `10` and `1_0` or `0` and `-0` are the same. Several variants can be `None`, unless the attribute also has the `reverse` config. Values that aren't literals can't be compared, and are ignored.
+//! 
+//! ```rust, ignore
+//! #[derive(CustomAttrs)]
+//! #[attr(
+//!     #[unique]
+//!     pub id: u32
+//! )]
+//! enum Enum {
+//!     #[attr(id = 1)]
+//!     VariantA,
+//! 
+//!     #[attr(id = 1)] // error
+//!     VariantB,
+//! }
+//! ```
+//! 
+//! ### Constant getters
+//! 
+//! With the `const_fn` config, the getter is a `const fn`, so it can be used in constants.
+//! 
+//! ```rust, ignore
+//! #[derive(CustomAttrs)]
+//! #[attr(
+//!     #[const_fn]
+//!     pub size: usize
+//! )]
+//! enum Kind {
+//!     #[attr(size = 16)]
+//!     Big,
+//! }
+//! 
+//! const MAX: usize = Kind::Big.get_size();
+//! ```
+//! 
+//! The values must be usable in a constant context, and can't contain self references.
+//! 
+//! ### Lookup tables
+//! 
+//! With the `table` config, the values of the attribute are stored in a constant array named `<ATTRIBUTE>_TABLE`, in the order of the variants, and the getter reads the value from this array.
+//! 
+//! ```rust, ignore
+//! #[derive(CustomAttrs)]
+//! #[attr(
+//!     #[table]
+//!     pub weight: u32
+//! )]
+//! enum Item {
+//!     #[attr(weight = 3)]
+//!     Sword,
+//! 
+//!     #[attr(weight = 1)]
+//!     Potion,
+//! }
+//! 
+//! Item::WEIGHT_TABLE; // [3, 1]
+//! ```
+//! 
+//! This config can only be used on enums whose variants have no fields, and the type of the attribute must be `Copy`.
+//! 
+//! ### Iterating over variants
+//! 
+//! The variants without fields are listed in the `VARIANTS` constant, which generic enums only have when they are `'static`, like `Enum::<String>::VARIANTS`. For each attribute, an `iter_<attribute>` function returns an iterator over these variants, with their value.
+//! 
+//! ```rust, ignore
+//! for variant in Enum::VARIANTS {
+//!     // ...
+//! }
+//! 
+//! for (variant, value) in Enum::iter_a() {
+//!     // ...
+//! }
+//! ```
+//! 
+//! Attributes using self references can be iterated too, as the variants without fields have no fields to reference.
+//! 
+//! Attributes whose type has an elided lifetime, like `&str` or `Cow<str>`, have no `iter_<attribute>` function, as it has no argument to borrow the value from. Write the lifetime as `'static` to iterate over them. Types other than `Cow` which hide their lifetime must write it as `'_`, like `Foo<'_>`.
+//! 
+//! These items can be skipped if the type already defines items with these names, see [Skipping generated items](#skipping-generated-items).
+//! 
+//! ### Attribute introspection
+//! 
+//! The attributes declared on a type can be listed at runtime with the `attribute_info` function, which returns their name, getter name, type, documentation, and whether they are optional or have a default value.
+//! 
+//! ```rust, ignore
+//! for info in Enum::attribute_info() {
+//!     println!("{}: {} ({})", info.name(), info.type_name(), info.doc());
+//! }
+//! ```
+//! 
+//! The companion enum of field attributes has the same function, listing the field attributes.
+//! 
+//! ### Dynamic access
+//! 
+//! The `attr` function returns the value of an attribute from its name, as an `AttrValue`.
+//! Integers, floats, booleans, characters, strings and `Option`s of those are converted to the matching kind of value, while any other type is boxed into `AttrValue::Any`.
+//! 
+//! ```rust, ignore
+//! let size = Enum::Variant.attr("size").and_then(|value| value.as_uint());
+//! ```
+//! 
+//! Attributes whose type uses a generic parameter or a lifetime that isn't `'static`, elided ones included like in `Cow<str>`, and isn't one of the kinds above, can't be boxed and are returned as `AttrValue::Unsupported`. `None` is only returned for names that aren't declared, and on variants opting out of the attribute.
+//! 
+//! ### Skipping generated items
+//! 
+//! `VARIANTS`, `iter_<attribute>`, `attribute_info` and `attr` are generated in an `impl` block of the type. A type which already defines an item with one of these names can skip it with `skip` :
+//! 
+//! ```rust, ignore
+//! #[derive(CustomAttrs)]
+//! #[attr(skip(VARIANTS, attr))]
+//! #[attr(code: u32)]
+//! enum Enum {
+//!     // ...
+//! }
+//! ```
+//! 
+//! A getter named like one of the generated items which isn't skipped is an error.
+//! 
+//! ### Structs
+//! 
+//! The derive also works on structs. As a struct has no variants, the value of each attribute is the one set in its declaration.
+//! 
+//! ```rust, ignore
+//! #[derive(CustomAttrs)]
+//! #[attr(pub table: &'static str = "records")]
+//! #[attr(pub version: Option<u32>)]
+//! struct Record {
+//!     id: String
+//! }
+//! ```
+//! 
+//! For each attribute, a getter taking `&self` is generated, as well as an associated function named after the attribute.
+//! A getter renamed with the `function` config can't take the name of one of these associated functions.
+//! 
+//! ```rust, ignore
+//! Record::table();
+//! record.get_table();
+//! ```
+//! 
+//! Self references can be used in the values, and resolve against the fields of the struct. The associated function isn't generated in that case.
+//! 
+//! ```rust, ignore
+//! #[derive(CustomAttrs)]
+//! #[attr(pub id_len: usize = #self.id.len())]
+//! struct Record {
+//!     id: String
+//! }
+//! ```
+//! 
+//! ### Field attributes
+//! 
+//! Attributes declared with the `field` config are set on the fields of the struct, or on the fields of the variants, instead of the variants.
+//! 
+//! ```rust, ignore
+//! #[derive(CustomAttrs)]
+//! #[attr(
+//!     #[field]
+//!     pub label: &'static str
+//! )]
+//! #[attr(
+//!     #[field]
+//!     pub sensitive: bool = false
+//! )]
+//! struct Form {
+//!     #[attr(label = "Name")]
+//!     name: String,
+//! 
+//!     #[attr(label = "Password", sensitive = true)]
+//!     password: String,
+//! }
+//! ```
+//! 
+//! A companion enum named `<type>Field` is generated, with one variant per field. Fields of a tuple struct are named `Field<index>`, and fields of a variant are prefixed by the variant name. The getters of the field attributes are generated on this enum.
+//! 
+//! The `name` of a field of a variant is prefixed by the variant name and a dot, like `Transfer.0`. Two fields giving the same companion variant, like `bar_baz` on `Foo` and `baz` on `FooBar`, are an error.
+//! 
+//! ```rust, ignore
+//! FormField::Password.get_sensitive();
+//! Form::field_label(FormField::Name);
+//! 
+//! for (name, field) in Form::field_attrs() {
+//!     // ...
+//! }
+//! ```
+//! 
+//! ### Warnings
+//! 
+//! With the `lints` feature, the derive warns about values which compile, but are redundant or likely a mistake :
+//! - an optional attribute without default value that no variant sets,
+//! - an attribute set to the same value on every variant, which could be its default value,
+//! - a value equal to the default value of the attribute,
+//! - a value of an optional attribute wrapped into `Some`, which is done automatically.
+//! 
+//! The attributes of structs and the field attributes are checked too, except for the lints comparing the values of several variants.
+//! 
+//! As procedural macros can't emit warnings on stable, these are reported as uses of deprecated constants. They can be silenced with `#[allow(deprecated)]` on the type.
+//! 
+//! ```rust, ignore
+//! #[derive(CustomAttrs)]
+//! #[attr(pub label: Option<&'static str>)]
+//! #[allow(deprecated)]
+//! enum Enum {
+//!     #[attr(label = Some("a"))]
+//!     Variant,
+//! }
+//! ```
+//! 
 //! ## Examples
 //! 
 //! ```rust
@@ -263,10 +647,21 @@
 //! 
 //! See the examples directory for more details.
 //! 
+//! # Migrating from 1.6
+//! 
+//! The derive macro now lives in the `custom_attrs_derive` crate. `custom_attrs` re-exports it, with the types used by the generated code : `AttributeInfo`, `AttrValue` and `MissingAttr`.
+//! - Keep depending on `custom_attrs` and importing `custom_attrs::CustomAttrs`, rather than depending on `custom_attrs_derive`.
+//! - The generated code refers to `::custom_attrs`, so the dependency can't be renamed in `Cargo.toml`.
+//! 
+//! The derive also generates new items in the `impl` block of the type : `VARIANTS` and `iter_<attribute>` on enums, `attribute_info` and `attr`. A type which already defines an item with one of these names can skip it, see [Skipping generated items](#skipping-generated-items).
+//! 
+//! The other new items are only generated by the configs and syntaxes using them : `from_<attribute>` with `reverse`, `<ATTRIBUTE>_TABLE` with `table`, `try_get_<attribute>` when a variant opts out of the attribute, and the `<type>Field` companion enum with the `field_<attribute>` and `field_attrs` functions when field attributes are declared.
+//! 
 //! # Features
 //! 
-//! - `help_span` : Merge main error and help notes, while these have they own span, and do not produce a separated error.
-//! This features is nightly only.
+//! - `help_span` : Point the help notes of duplicate errors at their own span, instead of only writing the line and column
+//!   of the first declaration in the note. The span is only shown on nightly, so the help keeps the line and column.
+//! - `lints` : Warn about redundant or suspicious attribute values, described in the Warnings section.
 //! 
 //! # License
 //! 
@@ -274,272 +669,13 @@
 
 #![allow(clippy::needless_doctest_main)]
 
-use proc_macro::TokenStream;
-use proc_macro_error::proc_macro_error;
-use syn::DeriveInput;
 
-mod config;
-mod derive;
-mod opt;
-mod reference;
-mod value;
+pub use custom_attrs_derive::CustomAttrs;
 
-/// The main derive trait of the library.
-///
-/// ### Attribute declaration.
-/// 
-/// Each attribute declaration goes between the derive and the enum.
-/// 
-/// ```rust, ignore
-/// use custom_attrs::CustomAttrs;
-/// 
-/// #[derive(CustomAttrs)]
-/// 
-/// // attribute declarations 
-/// 
-/// enum Enum {
-///     // ...
-/// }
-/// ```
-/// 
-/// By default, an attribute declaration is composed of two parts : an attribute's name and it's type.
-/// 
-/// ```rust, ignore
-/// #[attr(name: u32)]
-/// ```
-/// 
-/// You can declare many attribute declarations in a single `attr`.
-/// 
-/// ```rust, ignore
-/// #[attr(
-///     name: u32,
-///     name2: usize
-/// )]
-/// ```
-/// 
-/// Optionally, you can add more components.
-/// 
-/// ### Optional components
-/// 
-/// You can set the visibility before the attribute name. This will change the visibility of the getter function.
-/// 
-/// ```rust, ignore
-/// #[attr(pub attribute: u32)]
-/// ```
-/// 
-/// By default, each attribute declared require a value to be set for each variant.
-/// If this requirement is not set, the library will produce an error.
-/// 
-/// You can disable this behavior by making it optional, by writing type into an `Option`, or by adding a default value behind the attribute declaration. See the example below.
-/// 
-/// Optional attribute syntax :
-/// ```rust, ignore
-/// #[attr(attribute: Option<u32>)]
-/// enum Enum {}
-/// ```
-/// 
-/// With default value syntax :
-/// ```rust, ignore
-/// #[attr(attribute: u32 = 3)]
-/// ```
-/// 
-/// You can add documentation to attributes declaration. It will be added to the getter function.
-/// 
-/// ```rust, ignore
-/// #[attr(
-///     /// Attribute documentation
-///     attribute: u32
-/// )]
-/// ```
-/// 
-/// ### Setting a value
-/// 
-/// To set a value for a variant, just add the name of the attribute followed by the value you want to set.
-/// 
-/// ```rust, ignore
-/// enum Enum {
-///     #[attr(attribute = 4)]
-///     VariantA
-/// }
-/// ```
-/// 
-/// Like declarations, you can set many values at once.
-/// 
-/// ```rust, ignore
-/// enum Enum {
-///     #[attr(
-///         attr1 = 4,
-///         attr2 = 1
-///     )]
-///     VariantA
-/// }
-/// ```
-/// 
-/// If the attribute is optional, you don't have to wrap it in a `Some`. `custom_attrs` will do this for you _(value wrapped in a Some are still supported)_. If you want the value to be `None`, just put `None` behind the it.
-/// 
-/// ```rust, ignore
-/// #[attr(optional: Option<usize>)]
-/// enum Enum {
-///     #[attr(optional = 4)]
-///     VariantA,
-/// 
-///     #[attr(optional = None)]
-///     VariantB,
-/// 
-///     #[attr(optional = Some(5))]
-///     VariantC,
-/// }
-/// ```
-/// 
-/// #### Self References
-/// 
-/// In attribute values you set, you can add a reference to a field of the variant.
-/// 
-/// The syntax is the following :
-/// 
-/// ```rust, ignore
-/// #[attr(name: usize)]
-/// enum Enum {
-///     // Use the name of the field if it's named
-///     #[attr(name = #self.field)]
-///     Variant {
-///         field: usize
-///     },
-/// 
-///     // Otherwise use it's position
-///     #[attr(name = #self.0)]
-///     Variant2(usize)
-/// }
-/// ```
-/// 
-/// Self references are processed before the value is parsed as expression, so you can use them anywhere you need :
-/// 
-/// ```rust, ignore
-/// enum Enum {
-///     #[attr(a = #self.list[*#self.index])]
-///     Variant3 {
-///         list: [usize; 4],
-///         index: usize,
-///     },
-/// }
-/// ```
-/// 
-/// If you're using self reference, the value returned will also be a reference. You can deref it like so :
-/// 
-/// ```rust, ignore
-/// #[attr(name = *#self.<field>)]
-/// ```
-/// 
-/// ### Attribute configuration
-/// 
-/// You can configure you attributes to change their characteristics.
-/// 
-/// The syntax of a property is the following :
-/// ```rust, ignore
-/// #[attr(
-///     #[<config_name> = <value>]
-///     <attribute>: <type>
-/// )]
-/// ```
-/// 
-/// Configs can also be flags: 
-/// ```rust, ignore
-/// #[attr(
-///     #[<config_name>]
-///     <attribute>: <type>
-/// )]
-/// ```
-/// 
-/// Like attributes, you can define many properties in one bloc or add many config blocs.
-/// 
-/// ```rust, ignore
-/// #[attr(
-///     #[<config_name>, <config_name2> = <value>]
-///     <attribute>: <type>
-/// ])
-/// ```
-/// 
-/// ```rust, ignore
-/// #[attr(
-///     #[<config_name>]
-///     #[<config_name2> = <value>]
-///     <attribute>: <type>
-/// ])
-/// ```
-/// 
-/// Here is a list of all the properties :
-/// - `function` : defines the name of the function to get the attribute
-/// 
-/// ### Getting a value attribute
-/// 
-/// To get the value from a variant, simple call `get_<attribute name>` or the name you've set in the properties of the attributes.
-/// 
-/// ```rust, ignore
-/// Element::VariantA.get_a();
-/// ```
-/// 
-/// The type this function returns is defined in the attribute declaration.
-/// 
-/// If you've set a documentation on the attribute, it will be shown on this function.
-/// 
-/// ## Examples
-/// 
-/// ```rust
-/// use custom_attrs::CustomAttrs;
-/// 
-/// // ...
-/// 
-/// #[derive(CustomAttrs)]
-/// 
-/// #[attr(
-///     #[function = "a_getter"]
-///     pub a: usize
-/// )]
-/// #[attr(b: Option<usize>)]
-/// #[attr(c: &'static str = "Hello world!")]
-/// enum Enum {
-///     #[attr(a = 5)]
-///     #[attr(b = 3)]
-///     Variant1,
-/// 
-///     #[attr(a = 3)]
-///     #[attr(c = "Hello again !")]
-///     Variant2,
-/// 
-///     #[attr(
-///         a = 1,
-///         b = 5,
-///         c = "Hello for the last time !"
-///     )]
-///     Variant3,
-/// 
-///     /// You can access fields of the variant
-///     #[attr(a = *#self.field)]
-///     Variant4 {
-///         field: usize
-///     }
-/// }
-/// 
-/// fn main() {
-///     Enum::Variant1.a_getter(); // custom getter name
-///     Enum::Variant2.get_b(); // default getter name
-/// }
-/// ```
-/// 
-/// See the examples directory for more details.
-/// 
-/// # Features
-/// 
-/// - `help_span` : Merge main error and help notes, while these have they own span, and do not produce a separated error.
-/// This features is nightly only.
-/// 
-/// # License
-/// 
-/// Licensed under the MIT license.
-#[proc_macro_derive(CustomAttrs, attributes(attr))]
-#[proc_macro_error]
-pub fn derive_custom_attrs(input: TokenStream) -> TokenStream {
-    let derive_input = syn::parse_macro_input!(input as DeriveInput);
+mod dynamic;
+mod info;
+mod missing;
 
-    derive::derive_custom_attrs(derive_input).into()
-}
+pub use dynamic::AttrValue;
+pub use info::AttributeInfo;
+pub use missing::MissingAttr;
@@ -0,0 +1,34 @@
+use std::fmt::{self, Display};
+
+/// The error returned by the `try_get_<attribute>` functions, when the variant opts out of the
+/// attribute with `#[attr(skip)]` or `#[attr(<attribute> = !)]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MissingAttr {
+    variant: &'static str,
+    attribute: &'static str,
+}
+
+impl MissingAttr {
+    #[doc(hidden)]
+    pub const fn __new(variant: &'static str, attribute: &'static str) -> Self {
+        Self { variant, attribute }
+    }
+
+    /// The name of the variant without value.
+    pub const fn variant(&self) -> &'static str {
+        self.variant
+    }
+
+    /// The name of the attribute, as written in its declaration.
+    pub const fn attribute(&self) -> &'static str {
+        self.attribute
+    }
+}
+
+impl Display for MissingAttr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` has no value for `{}`.", self.variant, self.attribute)
+    }
+}
+
+impl std::error::Error for MissingAttr {}
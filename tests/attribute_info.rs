@@ -0,0 +1,65 @@
+use custom_attrs::{AttributeInfo, CustomAttrs};
+
+#[derive(CustomAttrs)]
+#[attr(
+    /// The size, in bytes.
+    pub size: usize
+)]
+#[attr(
+    #[function = "label"]
+    name: Option<&'static str>
+)]
+#[attr(weight: u32 = 1)]
+#[allow(unused)]
+enum Enum {
+    #[attr(size = 4)]
+    A,
+
+    #[attr(size = 8, name = "b")]
+    B,
+}
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[field]
+    label: &'static str
+)]
+#[allow(unused)]
+struct Form {
+    #[attr(label = "Name")]
+    name: String,
+}
+
+#[test]
+fn attribute_info_lists_declarations() {
+    let info = Enum::attribute_info();
+
+    assert_eq!(info.len(), 3);
+
+    assert_eq!(info[0].name(), "size");
+    assert_eq!(info[0].getter(), "get_size");
+    assert_eq!(info[0].type_name(), "usize");
+    assert_eq!(info[0].doc(), " The size, in bytes.");
+    assert!(!info[0].is_optional());
+    assert!(!info[0].has_default());
+
+    assert_eq!(info[1].name(), "name");
+    assert_eq!(info[1].getter(), "label");
+    assert_eq!(info[1].type_name(), "Option<&'static str>");
+    assert_eq!(info[1].doc(), "");
+    assert!(info[1].is_optional());
+
+    assert_eq!(info[2].name(), "weight");
+    assert!(info[2].has_default());
+}
+
+#[test]
+fn attribute_info_of_field_attributes() {
+    assert_eq!(Form::attribute_info(), &[] as &[AttributeInfo]);
+
+    let info = FormField::attribute_info();
+    assert_eq!(info.len(), 1);
+    assert_eq!(info[0].name(), "label");
+    assert_eq!(info[0].type_name(), "&'static str");
+    assert_eq!(Form::field_label(FormField::Name), "Name");
+}
@@ -0,0 +1,43 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(name: &'static str)]
+#[attr(code: u32)]
+#[attr(label: String = format!("{} ({})", #attr.name, #attr.code))]
+#[attr(double: u32 = #attr.code * 2)]
+#[attr(alias: Option<&'static str>)]
+#[attr(alias_len: usize = #attr.alias.map(str::len).unwrap_or(0))]
+#[attr(len: usize = #attr.size + 1)]
+#[attr(size: usize = 0)]
+enum Enum {
+    #[attr(name = "first", code = 1)]
+    First,
+
+    #[attr(name = "second", code = 2, alias = "2nd")]
+    #[attr(label = format!("custom {}", #attr.name))]
+    Second,
+
+    #[attr(name = "third", code = 3, size = *#self.size)]
+    Third { size: usize },
+}
+
+#[test]
+fn attribute_references_in_defaults() {
+    assert_eq!(Enum::First.get_label(), "first (1)");
+    assert_eq!(Enum::First.get_double(), 2);
+    assert_eq!(Enum::Second.get_double(), 4);
+
+    assert_eq!(Enum::First.get_alias_len(), 0);
+    assert_eq!(Enum::Second.get_alias_len(), 3);
+}
+
+#[test]
+fn attribute_references_in_values() {
+    assert_eq!(Enum::Second.get_label(), "custom second");
+}
+
+#[test]
+fn attribute_references_with_self_references() {
+    assert_eq!(Enum::First.get_len(), 1);
+    assert_eq!(Enum::Third { size: 4 }.get_len(), 5);
+}
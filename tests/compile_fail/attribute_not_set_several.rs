@@ -0,0 +1,19 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub a: usize)]
+#[attr(pub b: &'static str)]
+#[attr(pub c: Option<bool>)]
+#[attr(pub d: char)]
+#[attr(pub e: u8 = 0)]
+enum Enum {
+    Variant1,
+
+    #[attr(b = "b")]
+    Variant2,
+
+    #[attr(a = 1, b = "b", d = 'd')]
+    Variant3,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(a: u32 = #attr.b + 1)]
+#[attr(b: u32 = #attr.c)]
+#[attr(c: u32)]
+enum Enum {
+    #[attr(c = 1)]
+    Variant1,
+
+    #[attr(c = #attr.a)]
+    Variant2,
+}
+
+fn main() {}
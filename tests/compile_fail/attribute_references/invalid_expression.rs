@@ -0,0 +1,11 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(name: &'static str)]
+#[attr(code: u32 = Self::#attr.name as u32)]
+enum Enum {
+    #[attr(name = "a")]
+    Variant1,
+}
+
+fn main() {}
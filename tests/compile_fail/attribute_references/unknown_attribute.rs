@@ -0,0 +1,11 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(a: u32 = #attr.missing)]
+#[attr(b: u32)]
+enum Enum {
+    #[attr(b = #attr.other * 2)]
+    Variant1,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[convert = "from"]
+    pub a: String
+)]
+#[attr(
+    #[convert = "into", wrap = Box::new]
+    pub b: Box<String>
+)]
+#[attr(
+    #[wrap = "Box::new"]
+    pub c: Box<u32>
+)]
+enum Enum {
+    #[attr(a = "a", b = "b", c = 1)]
+    Variant,
+}
+
+fn main() {}
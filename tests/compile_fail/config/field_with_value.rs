@@ -0,0 +1,16 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[field = "yes"]
+    pub label: &'static str = ""
+)]
+#[attr(
+    #[field, field]
+    pub unit: &'static str = ""
+)]
+struct Struct {
+    field: usize,
+}
+
+fn main() {}
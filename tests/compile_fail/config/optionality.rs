@@ -0,0 +1,17 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[optional, required]
+    pub a: Option<u32>
+)]
+#[attr(
+    #[no_wrap]
+    pub b: u32
+)]
+enum Enum {
+    #[attr(a = 1, b = 2)]
+    Variant,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[required]
+    pub a: Option<u32>
+)]
+enum Enum {
+    #[attr(a = None)]
+    Variant1,
+
+    Variant2,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[uniqeu]
+    pub a: usize
+)]
+enum Enum {
+    #[attr(a = 5)]
+    Variant1,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[const_fn]
+    pub name: String
+)]
+enum Kind {
+    #[attr(name = String::from("big"))]
+    Big,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[const_fn]
+    pub size: usize
+)]
+enum Kind {
+    #[attr(size = 16)]
+    Big,
+
+    #[attr(size = *#self.0)]
+    Custom(usize),
+}
+
+fn main() {}
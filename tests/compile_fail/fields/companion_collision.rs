@@ -0,0 +1,13 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[field]
+    pub label: &'static str = "?"
+)]
+enum Enum {
+    Foo { bar_baz: u32 },
+    FooBar { baz: u32 },
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub a: usize = 0)]
+#[attr(
+    #[field]
+    pub label: &'static str = ""
+)]
+enum Enum {
+    #[attr(label = "Variant")]
+    Variant1 {
+        #[attr(a = 5)]
+        field: usize,
+    },
+}
+
+fn main() {}
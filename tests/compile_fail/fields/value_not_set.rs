@@ -0,0 +1,15 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[field]
+    pub label: &'static str
+)]
+struct Struct {
+    #[attr(label = "Field")]
+    field1: usize,
+
+    field2: usize,
+}
+
+fn main() {}
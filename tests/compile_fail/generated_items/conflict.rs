@@ -0,0 +1,23 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[function = "attr"]
+    pub a: u32
+)]
+#[attr(
+    #[function = "iter_a"]
+    pub b: u32 = 0
+)]
+enum Enum {
+    #[attr(a = 1)]
+    Variant1,
+}
+
+#[derive(CustomAttrs)]
+#[attr(pub attribute_info: u32 = 3)]
+struct Struct {
+    field: usize,
+}
+
+fn main() {}
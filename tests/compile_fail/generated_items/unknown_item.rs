@@ -0,0 +1,24 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(skip(VARIANT, iter_b))]
+#[attr(pub a: u32 = 0)]
+enum Enum {
+    Variant1,
+}
+
+#[derive(CustomAttrs)]
+#[attr(skip(VARIANTS))]
+#[attr(pub a: u32 = 0)]
+struct Struct {
+    field: usize,
+}
+
+#[derive(CustomAttrs)]
+#[attr(skip(attr, attr))]
+#[attr(pub a: u32 = 0)]
+enum Enum2 {
+    Variant1,
+}
+
+fn main() {}
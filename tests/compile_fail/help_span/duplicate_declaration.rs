@@ -0,0 +1,12 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub a: usize)]
+#[attr(pub a: usize)]
+enum Enum {
+    #[attr(a = 3)]
+    #[attr(a = 4)]
+    Variant1,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub a: u32)]
+#[attr(pub b: u32 = #attr.a * 2)]
+enum Enum {
+    #[attr(a = 1)]
+    Variant1,
+
+    #[attr(a = !)]
+    Variant2,
+}
+
+fn main() {}
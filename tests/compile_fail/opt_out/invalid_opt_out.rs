@@ -0,0 +1,17 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub a: u32)]
+#[attr(pub b: Option<u32>)]
+enum Enum {
+    #[attr(a = 1, a = !)]
+    Variant1,
+
+    #[attr(b = !, c = !)]
+    Variant2,
+
+    #[attr(skip, skip)]
+    Variant3,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(code: u32)]
+enum Command {
+    #[attr(code = 1)]
+    Open,
+
+    #[attr(code = !)]
+    Save,
+}
+
+fn main() {
+    // only `try_get_code` is generated, as `Save` has no code
+    let _code = Command::Open.get_code();
+}
@@ -0,0 +1,16 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[table]
+    pub a: u32
+)]
+enum Enum {
+    #[attr(a = 1)]
+    Variant1,
+
+    #[attr(skip)]
+    Variant2,
+}
+
+fn main() {}
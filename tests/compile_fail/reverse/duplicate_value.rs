@@ -0,0 +1,24 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[reverse]
+    pub code: u32 = 0
+)]
+#[attr(
+    #[reverse]
+    pub name: Option<&'static str>
+)]
+enum Enum {
+    #[attr(code = 1, name = "a")]
+    Variant1,
+
+    #[attr(code = 0x1, name = Some("a"))]
+    Variant2,
+
+    Variant3,
+
+    Variant4,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub a: usize = *#self.1)]
+enum Enum {
+    #[attr(a = 1)]
+    Variant1(usize),
+
+    Variant2(usize),
+    Variant3(usize, usize),
+    Variant4 { field: usize },
+}
+
+fn main() {}
@@ -6,9 +6,6 @@ enum Enum {
     #[attr(a = #)]
     Variant1(usize, u32),
 
-    #[attr(a = #self)]
-    Variant3(usize, u32),
-
     #[attr(a = #self.)]
     Variant4(usize, u32),
 
@@ -0,0 +1,23 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub a: usize)]
+enum Enum {
+    #[attr(a = *#self.lenght)]
+    Variant1 { length: usize },
+
+    #[attr(a = *#self.other)]
+    Variant2 { length: usize, count: usize },
+
+    #[attr(a = *#self.2)]
+    Variant3(usize, usize),
+
+    #[attr(a = *#self.0)]
+    Variant4,
+}
+
+#[derive(CustomAttrs)]
+#[attr(pub b: usize = *#self.1)]
+struct Struct(usize);
+
+fn main() {}
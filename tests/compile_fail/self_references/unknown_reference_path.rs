@@ -0,0 +1,9 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub a: u32 = #foo.bar)]
+enum Enum {
+    Variant1,
+}
+
+fn main() {}
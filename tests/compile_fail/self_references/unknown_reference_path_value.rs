@@ -0,0 +1,13 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub a: u32)]
+enum Enum {
+    #[attr(a = #foo.bar)]
+    Variant1,
+
+    #[attr(a = #foo.bar.0 + #self.0)]
+    Variant2(u32),
+}
+
+fn main() {}
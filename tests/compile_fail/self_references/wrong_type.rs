@@ -0,0 +1,14 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub a: usize)]
+#[attr(pub b: Option<u8> = #self.0)]
+enum Enum {
+    #[attr(a = #self.name, b = 1)]
+    Variant1 { name: &'static str },
+
+    #[attr(a = #self.0 + 1)]
+    Variant2(&'static str),
+}
+
+fn main() {}
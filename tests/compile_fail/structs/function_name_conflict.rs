@@ -0,0 +1,17 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[function = "a"]
+    pub a: usize = 1,
+
+    pub b: usize = 2,
+
+    #[function = "b"]
+    pub c: usize = 3,
+)]
+struct Struct {
+    field: usize,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[reverse]
+    pub code: u32 = 0
+)]
+struct Struct {
+    field: usize,
+}
+
+fn main() {}
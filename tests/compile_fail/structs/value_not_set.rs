@@ -0,0 +1,10 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub a: usize)]
+#[attr(pub b: Option<usize>)]
+struct Struct {
+    field: usize,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[table]
+    pub name: String = String::new()
+)]
+enum Enum {
+    Variant1,
+    Variant2,
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use custom_attrs::CustomAttrs;
+
+fn name(value: &Enum) -> &'static str {
+    match value {
+        Enum::Variant1 => "1",
+        Enum::Variant2 => "2",
+    }
+}
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[table]
+    a: &'static str = name(#self)
+)]
+enum Enum {
+    Variant1,
+    Variant2,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[table]
+    pub weight: u32 = 1
+)]
+enum Item {
+    Sword,
+
+    Custom(u32),
+
+    Other { weight: u32 },
+}
+
+fn main() {}
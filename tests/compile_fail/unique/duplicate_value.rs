@@ -0,0 +1,31 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[unique]
+    pub id: u32
+)]
+#[attr(
+    #[unique]
+    pub code: &'static str = "default"
+)]
+#[attr(
+    #[unique]
+    pub offset: i32
+)]
+#[attr(
+    #[unique]
+    pub rank: Option<u32>
+)]
+enum Enum {
+    #[attr(id = 1, code = "a", offset = 1_0, rank = 10)]
+    Variant1,
+
+    #[attr(id = 2, code = "default", offset = -0, rank = 1_0)]
+    Variant2(usize),
+
+    #[attr(id = 1, offset = 0)]
+    Variant3 { field: usize },
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(a: usize = 0)]
+#[attr(c: usize = 0)]
+enum Enum {
+    // one letter names are too different from each other to be suggested
+    #[attr(b = 3)]
+    Variant1,
+}
+
+fn main() {}
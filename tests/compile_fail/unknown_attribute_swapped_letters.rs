@@ -0,0 +1,14 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(width: usize = 0)]
+#[attr(height: usize = 0)]
+enum Enum {
+    #[attr(wdith = 3)]
+    Variant1,
+
+    #[attr(heigth = 3)]
+    Variant2,
+}
+
+fn main() {}
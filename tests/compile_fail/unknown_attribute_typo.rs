@@ -0,0 +1,14 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(label: &'static str)]
+#[attr(code: u32)]
+#[attr(description: Option<&'static str>)]
+enum Enum {
+    #[attr(lable = "first", code = 1)]
+    #[attr(descriptoin = "the first variant")]
+    #[attr(name = "first")]
+    Variant1,
+}
+
+fn main() {}
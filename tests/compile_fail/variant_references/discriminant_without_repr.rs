@@ -0,0 +1,10 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(code: u8 = #variant.discriminant)]
+enum Enum {
+    Variant1,
+    Variant2,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(value: u32 = Self::#variant.name as u32)]
+enum Enum {
+    Variant1,
+    Variant2,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(name: &'static str)]
+enum Enum {
+    #[attr(name = #variant.title_name)]
+    Variant1,
+}
+
+fn main() {}
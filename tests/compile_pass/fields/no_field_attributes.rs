@@ -0,0 +1,14 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub a: u32 = 0)]
+#[allow(unused)]
+enum Enum {
+    Foo { bar_baz: u32 },
+    FooBar { baz: u32 },
+}
+
+fn main() {
+    let _a = Enum::Foo { bar_baz: 1 }.get_a();
+    let _a = Enum::FooBar { baz: 1 }.get_a();
+}
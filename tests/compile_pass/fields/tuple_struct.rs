@@ -0,0 +1,14 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[field]
+    pub label: Option<&'static str>
+)]
+#[allow(unused)]
+struct Tuple(#[attr(label = "First")] u32, u32);
+
+fn main() {
+    let _label = TupleField::Field0.get_label();
+    let _label = Tuple::field_label(TupleField::Field1);
+}
@@ -0,0 +1,32 @@
+use std::borrow::Cow;
+
+use custom_attrs::CustomAttrs;
+
+// `iter_name` and `iter_label` aren't generated, so the type can define them
+#[derive(CustomAttrs)]
+#[attr(pub code: u32)]
+#[attr(pub name: &str = "?")]
+#[attr(pub label: Cow<str> = Cow::Borrowed("?"))]
+#[allow(unused)]
+enum Enum {
+    #[attr(code = 1)]
+    Variant1,
+
+    #[attr(code = 2, name = "second", label = Cow::Borrowed("second"))]
+    Variant2,
+}
+
+#[allow(unused)]
+impl Enum {
+    fn iter_name() {}
+
+    fn iter_label() {}
+}
+
+fn main() {
+    assert_eq!(Enum::Variant2.get_name(), "second");
+    assert_eq!(Enum::Variant2.get_label(), "second");
+
+    let _codes = Enum::iter_code().collect::<Vec<_>>();
+    let _label = Enum::Variant2.attr("label");
+}
@@ -0,0 +1,42 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(skip(VARIANTS, iter_code, attribute_info, attr))]
+#[attr(pub code: u32)]
+#[attr(pub name: &'static str = "?")]
+#[allow(unused)]
+enum Enum {
+    #[attr(code = 1)]
+    Variant1,
+
+    #[attr(code = 2, name = "second")]
+    Variant2,
+}
+
+// items of the type which would conflict with the generated ones
+#[allow(unused)]
+impl Enum {
+    const VARIANTS: usize = 2;
+
+    fn iter_code() {}
+
+    fn attribute_info() {}
+
+    fn attr(&self) {}
+}
+
+#[derive(CustomAttrs)]
+#[attr(skip(attr))]
+#[attr(pub attr: u32 = 3)]
+#[allow(unused)]
+struct Struct {
+    field: usize,
+}
+
+fn main() {
+    let _code = Enum::Variant1.get_code();
+    let _names = Enum::iter_name().collect::<Vec<_>>();
+
+    let _attr = Struct::attr();
+    let _info = Struct::attribute_info();
+}
@@ -0,0 +1,31 @@
+use custom_attrs::CustomAttrs;
+
+// counts the `#` tokens it is given
+macro_rules! count_pounds {
+    () => { 0_usize };
+    (# $($rest:tt)*) => { 1 + count_pounds!($($rest)*) };
+    ($other:tt $($rest:tt)*) => { count_pounds!($($rest)*) };
+}
+
+#[derive(CustomAttrs)]
+#[attr(pub pounds: usize)]
+#[attr(pub text: &'static str = stringify!(##value))]
+enum Enum {
+    // `#x` isn't a known reference, so it is kept in the macro body
+    #[attr(pounds = count_pounds!(#x #y z))]
+    Variant1,
+
+    // `##` is an escaped `#`, while `#self.0` is still a reference
+    #[attr(pounds = count_pounds!(## self) + *#self.0)]
+    Variant2(usize),
+
+    #[attr(pounds = count_pounds!(#(#x)*))]
+    Variant3,
+}
+
+fn main() {
+    assert_eq!(Enum::Variant1.get_pounds(), 2);
+    assert_eq!(Enum::Variant2(3).get_pounds(), 4);
+    assert_eq!(Enum::Variant3.get_pounds(), 1);
+    assert!(Enum::Variant1.get_text().starts_with('#'));
+}
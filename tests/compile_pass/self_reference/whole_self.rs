@@ -0,0 +1,25 @@
+use custom_attrs::CustomAttrs;
+
+fn first(value: &Enum) -> usize {
+    match value {
+        Enum::Variant1(first, _) => *first,
+        Enum::Variant2 { .. } => 0,
+    }
+}
+
+#[derive(CustomAttrs)]
+#[attr(pub a: usize)]
+enum Enum {
+    #[attr(a = first(#self))]
+    Variant1(usize, u32),
+
+    #[attr(a = first(#self))]
+    Variant2 {
+        #[allow(unused)]
+        field1: u32,
+    },
+}
+
+fn main() {
+    assert_eq!(Enum::Variant1(3, 4).get_a(), 3);
+}
@@ -0,0 +1,19 @@
+#![allow(unused)]
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub a: usize = 5)]
+#[attr(pub b: Option<usize>)]
+struct Struct {
+    field: usize,
+}
+
+#[derive(CustomAttrs)]
+#[attr(pub a: usize = 5)]
+struct Unit;
+
+fn main() {
+    let a = Struct { field: 1 }.get_a();
+    let b = Struct::b();
+    let c = Unit::a();
+}
@@ -0,0 +1,26 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub a: usize = *#self.field2)]
+#[attr(pub b: &usize = &#self.list[*#self.field2])]
+struct Struct {
+    #[allow(unused)]
+    field1: u32,
+
+    field2: usize,
+    list: [usize; 4],
+}
+
+#[derive(CustomAttrs)]
+#[attr(pub a: usize = *#self.1)]
+struct Tuple(u32, usize);
+
+fn main() {
+    let _a = Struct {
+        field1: 0,
+        field2: 2,
+        list: [0; 4],
+    }
+    .get_a();
+    let _b = Tuple(0, 1).get_a();
+}
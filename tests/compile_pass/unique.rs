@@ -0,0 +1,36 @@
+#![allow(unused)]
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[unique]
+    pub id: u32
+)]
+#[attr(
+    #[unique, reverse]
+    pub code: Option<&'static str>
+)]
+#[attr(
+    #[unique]
+    pub alias: Option<&'static str>
+)]
+enum Enum {
+    #[attr(id = 1, code = "a")]
+    Variant1,
+
+    // several variants can have no alias
+    #[attr(id = 2, alias = None)]
+    Variant2,
+
+    // values that aren't literals can't be checked
+    #[attr(id = 1 + 2, alias = None)]
+    Variant3,
+
+    #[attr(id = 3)]
+    Variant4(usize),
+}
+
+fn main() {
+    let _id = Enum::Variant1.get_id();
+    let _variant = Enum::from_code(Some("a"));
+}
@@ -1,8 +1,97 @@
+use std::{fs, path::PathBuf};
+
+const COMPILE_FAIL_DIR: &str = "tests/compile_fail";
+
+/// The features changing the output of some compile_fail tests, with these tests : `lints` adds its
+/// warnings, and `help_span` turns the notes of duplicate errors into helps. These tests are only
+/// checked without the feature, the snapshots taken with it are in `tests/compile_fail/<feature>`.
+const FEATURES: [(&str, bool, &[&str]); 2] = [
+    (
+        "lints",
+        cfg!(feature = "lints"),
+        &["not_right_type.rs", "not_right_visibility.rs"],
+    ),
+    (
+        "help_span",
+        cfg!(feature = "help_span"),
+        &[
+            "attribute_already_declared.rs",
+            "same_function_used.rs",
+            "variant_value_already_set.rs",
+            "config/already_set.rs",
+            "config/conversion.rs",
+            "config/field_with_value.rs",
+            "config/optionality.rs",
+            "fields/companion_collision.rs",
+            "generated_items/unknown_item.rs",
+            "opt_out/invalid_opt_out.rs",
+            "reverse/duplicate_value.rs",
+            "unique/duplicate_value.rs",
+        ],
+    ),
+];
+
+fn rust_files(dir: &str) -> Vec<String> {
+    let mut files = fs::read_dir(PathBuf::from(COMPILE_FAIL_DIR).join(dir))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .map(|path| {
+            let name = path.file_name().unwrap().to_str().unwrap();
+            match dir {
+                "" => name.to_owned(),
+                dir => format!("{}/{}", dir, name),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    files.sort();
+    files
+}
+
+/// Returns the compile_fail tests to check with the enabled features, relative to
+/// `tests/compile_fail`.
+fn compile_fail_tests() -> Vec<String> {
+    let mut dirs = fs::read_dir(COMPILE_FAIL_DIR)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .map(|path| path.file_name().unwrap().to_str().unwrap().to_owned())
+        .filter(|dir| {
+            FEATURES
+                .iter()
+                .all(|(feature, enabled, _)| dir != feature || *enabled)
+        })
+        .collect::<Vec<_>>();
+    dirs.sort();
+
+    let changed_by_features = FEATURES
+        .iter()
+        .filter(|(_, enabled, _)| *enabled)
+        .flat_map(|(_, _, tests)| tests.iter())
+        .collect::<Vec<_>>();
+
+    let mut tests = rust_files("");
+    tests.extend(dirs.iter().flat_map(|dir| rust_files(dir)));
+    tests.retain(|test| !changed_by_features.iter().any(|changed| *changed == test));
+
+    tests
+}
+
 #[test]
 fn compile_test() {
     let test = trybuild::TestCases::new();
     test.pass("tests/compile_pass/*.rs");
     test.pass("tests/compile_pass/*/*.rs");
-    test.compile_fail("tests/compile_fail/*.rs");
-    test.compile_fail("tests/compile_fail/*/*.rs");
+
+    for path in compile_fail_tests() {
+        test.compile_fail(PathBuf::from(COMPILE_FAIL_DIR).join(path));
+    }
+
+    // the warnings denied by these tests are only emitted with the `lints` feature
+    if cfg!(feature = "lints") {
+        test.compile_fail("tests/lints/*.rs");
+    } else {
+        test.pass("tests/lints/*.rs");
+    }
 }
@@ -0,0 +1,38 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[const_fn]
+    pub size: usize
+)]
+#[attr(
+    #[const_fn]
+    pub label: Option<&'static str>
+)]
+enum Kind {
+    #[attr(size = 16, label = "big")]
+    Big,
+
+    #[attr(size = 4)]
+    Small,
+}
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[const_fn]
+    pub version: u32 = 2
+)]
+struct Record;
+
+const MAX: usize = Kind::Big.get_size();
+const BUFFER: [u8; Kind::Small.get_size()] = [0; Kind::Small.get_size()];
+const LABEL: Option<&str> = Kind::Small.get_label();
+const VERSION: u32 = Record::version();
+
+#[test]
+fn test_const_getters() {
+    assert_eq!(MAX, 16);
+    assert_eq!(BUFFER.len(), 4);
+    assert_eq!(LABEL, None);
+    assert_eq!(VERSION, 2);
+}
@@ -0,0 +1,82 @@
+use std::borrow::Cow;
+
+use custom_attrs::{AttrValue, CustomAttrs};
+
+#[derive(Debug, PartialEq)]
+struct Color(u8, u8, u8);
+
+#[derive(CustomAttrs)]
+#[attr(size: usize)]
+#[attr(offset: i32 = 0)]
+#[attr(ratio: f32 = 1.0)]
+#[attr(enabled: bool = true)]
+#[attr(name: &'static str)]
+#[attr(label: Option<String>)]
+#[attr(color: Color = Color(0, 0, 0))]
+#[allow(unused)]
+enum Enum {
+    #[attr(size = 4, name = "a", offset = -2)]
+    A,
+
+    #[attr(size = 8, name = "b", label = String::from("B"))]
+    #[attr(color = Color(255, 0, 0))]
+    B,
+}
+
+#[derive(CustomAttrs)]
+#[attr(label: Cow<str> = Cow::Borrowed("?"))]
+#[attr(static_label: Cow<'static, str> = Cow::Borrowed("?"))]
+#[allow(unused)]
+enum Labels {
+    Variant,
+}
+
+#[derive(CustomAttrs)]
+#[attr(pub name: &'a str = #self.name)]
+#[attr(value: T = #self.value.clone())]
+struct Generic<'a, T: Clone> {
+    name: &'a str,
+    value: T,
+}
+
+#[test]
+fn attr_by_name() {
+    assert_eq!(Enum::A.attr("size").unwrap().as_uint(), Some(4));
+    assert_eq!(Enum::A.attr("offset").unwrap().as_int(), Some(-2));
+    assert_eq!(Enum::A.attr("ratio").unwrap().as_float(), Some(1.0));
+    assert_eq!(Enum::A.attr("enabled").unwrap().as_bool(), Some(true));
+    assert_eq!(Enum::A.attr("name").unwrap().as_str(), Some("a"));
+
+    assert!(matches!(Enum::A.attr("label"), Some(AttrValue::Option(None))));
+    let label = Enum::B.attr("label").unwrap();
+    assert_eq!(label.as_option().and_then(AttrValue::as_str), Some("B"));
+
+    let color = Enum::B.attr("color").unwrap();
+    assert_eq!(color.downcast::<Color>(), Some(&Color(255, 0, 0)));
+
+    assert!(Enum::A.attr("unknown").is_none());
+}
+
+#[test]
+fn attr_of_generic_type() {
+    let generic = Generic {
+        name: "generic",
+        value: 5,
+    };
+
+    assert_eq!(generic.attr("name").unwrap().as_str(), Some("generic"));
+
+    // the type of `value` is a generic parameter, it can't be boxed into `Any`
+    assert!(matches!(generic.attr("value"), Some(AttrValue::Unsupported)));
+    assert!(generic.attr("unknown").is_none());
+    assert_eq!(generic.get_value(), 5);
+}
+
+#[test]
+fn attr_with_hidden_lifetime() {
+    // `Cow<str>` borrows for an elided lifetime, which may not be `'static`
+    assert!(matches!(Labels::Variant.attr("label"), Some(AttrValue::Unsupported)));
+
+    let label = Labels::Variant.attr("static_label").unwrap();
+    assert_eq!(label.downcast::<Cow<'static, str>>(), Some(&Cow::Borrowed("?")));
+}
@@ -0,0 +1,77 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub version: u32 = 1)]
+#[attr(
+    #[field]
+    pub label: &'static str
+)]
+#[attr(
+    #[field]
+    pub unit: Option<&'static str>
+)]
+#[attr(
+    #[field]
+    pub sensitive: bool = false
+)]
+#[allow(unused)]
+struct Form {
+    #[attr(label = "Name")]
+    name: String,
+
+    #[attr(label = "Weight", unit = "kg")]
+    weight_kg: u32,
+
+    #[attr(label = "Password", sensitive = true)]
+    password: String,
+}
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[field]
+    pub label: &'static str = "?"
+)]
+#[allow(unused)]
+enum Event {
+    Login {
+        #[attr(label = "User")]
+        user: String,
+    },
+
+    Transfer(#[attr(label = "From")] u32, u32),
+}
+
+#[test]
+fn test_field_attribute_get() {
+    assert_eq!(FormField::Name.get_label(), "Name");
+    assert_eq!(FormField::WeightKg.get_unit(), Some("kg"));
+    assert!(FormField::Password.get_sensitive());
+    assert!(!FormField::Name.get_sensitive());
+
+    assert_eq!(Form::field_label(FormField::WeightKg), "Weight");
+    assert_eq!(Form::field_unit(FormField::Name), None);
+}
+
+#[test]
+fn test_field_attribute_enum() {
+    assert_eq!(Event::field_label(EventField::LoginUser), "User");
+    assert_eq!(Event::field_label(EventField::Transfer0), "From");
+    assert_eq!(Event::field_label(EventField::Transfer1), "?");
+}
+
+#[test]
+fn test_field_attribute_iter() {
+    let fields = Form::field_attrs().collect::<Vec<_>>();
+
+    assert_eq!(
+        fields,
+        vec![
+            ("name", FormField::Name),
+            ("weight_kg", FormField::WeightKg),
+            ("password", FormField::Password),
+        ]
+    );
+
+    let names = Event::field_attrs().map(|(name, _)| name).collect::<Vec<_>>();
+    assert_eq!(names, vec!["Login.user", "Transfer.0", "Transfer.1"]);
+}
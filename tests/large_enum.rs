@@ -0,0 +1,115 @@
+//! A very large enum, to check the derive handles a large input. `scripts/expansion_size.sh`
+//! measures the size of its expansion and the time taken to build it.
+
+use custom_attrs::CustomAttrs;
+
+/// Declares `Large` with a variant setting every attribute for each ident, between a tuple
+/// variant and a struct variant taking the default values.
+///
+/// The values are written as literals rather than `#variant` references, so the enum can be
+/// expanded by revisions which don't support them.
+macro_rules! large_enum {
+    ($($variant: ident = $id: literal, $name: literal;)*) => {
+        #[derive(CustomAttrs)]
+        #[attr(pub id: usize)]
+        #[attr(pub name: &'static str = "unnamed")]
+        #[attr(pub weight: Option<usize>)]
+        #[allow(unused)]
+        enum Large {
+            #[attr(id = 0)]
+            First(u32),
+
+            $(
+                #[attr(id = $id, name = $name, weight = $id * 10)]
+                $variant,
+            )*
+
+            #[attr(id = 299)]
+            Last { field: u32 },
+        }
+    };
+}
+
+large_enum! {
+    A0 = 1, "a0"; A1 = 2, "a1"; A2 = 3, "a2"; A3 = 4, "a3"; A4 = 5, "a4";
+    A5 = 6, "a5"; A6 = 7, "a6"; A7 = 8, "a7"; A8 = 9, "a8"; A9 = 10, "a9";
+    B0 = 11, "b0"; B1 = 12, "b1"; B2 = 13, "b2"; B3 = 14, "b3"; B4 = 15, "b4";
+    B5 = 16, "b5"; B6 = 17, "b6"; B7 = 18, "b7"; B8 = 19, "b8"; B9 = 20, "b9";
+    C0 = 21, "c0"; C1 = 22, "c1"; C2 = 23, "c2"; C3 = 24, "c3"; C4 = 25, "c4";
+    C5 = 26, "c5"; C6 = 27, "c6"; C7 = 28, "c7"; C8 = 29, "c8"; C9 = 30, "c9";
+    D0 = 31, "d0"; D1 = 32, "d1"; D2 = 33, "d2"; D3 = 34, "d3"; D4 = 35, "d4";
+    D5 = 36, "d5"; D6 = 37, "d6"; D7 = 38, "d7"; D8 = 39, "d8"; D9 = 40, "d9";
+    E0 = 41, "e0"; E1 = 42, "e1"; E2 = 43, "e2"; E3 = 44, "e3"; E4 = 45, "e4";
+    E5 = 46, "e5"; E6 = 47, "e6"; E7 = 48, "e7"; E8 = 49, "e8"; E9 = 50, "e9";
+    F0 = 51, "f0"; F1 = 52, "f1"; F2 = 53, "f2"; F3 = 54, "f3"; F4 = 55, "f4";
+    F5 = 56, "f5"; F6 = 57, "f6"; F7 = 58, "f7"; F8 = 59, "f8"; F9 = 60, "f9";
+    G0 = 61, "g0"; G1 = 62, "g1"; G2 = 63, "g2"; G3 = 64, "g3"; G4 = 65, "g4";
+    G5 = 66, "g5"; G6 = 67, "g6"; G7 = 68, "g7"; G8 = 69, "g8"; G9 = 70, "g9";
+    H0 = 71, "h0"; H1 = 72, "h1"; H2 = 73, "h2"; H3 = 74, "h3"; H4 = 75, "h4";
+    H5 = 76, "h5"; H6 = 77, "h6"; H7 = 78, "h7"; H8 = 79, "h8"; H9 = 80, "h9";
+    I0 = 81, "i0"; I1 = 82, "i1"; I2 = 83, "i2"; I3 = 84, "i3"; I4 = 85, "i4";
+    I5 = 86, "i5"; I6 = 87, "i6"; I7 = 88, "i7"; I8 = 89, "i8"; I9 = 90, "i9";
+    J0 = 91, "j0"; J1 = 92, "j1"; J2 = 93, "j2"; J3 = 94, "j3"; J4 = 95, "j4";
+    J5 = 96, "j5"; J6 = 97, "j6"; J7 = 98, "j7"; J8 = 99, "j8"; J9 = 100, "j9";
+    K0 = 101, "k0"; K1 = 102, "k1"; K2 = 103, "k2"; K3 = 104, "k3"; K4 = 105, "k4";
+    K5 = 106, "k5"; K6 = 107, "k6"; K7 = 108, "k7"; K8 = 109, "k8"; K9 = 110, "k9";
+    L0 = 111, "l0"; L1 = 112, "l1"; L2 = 113, "l2"; L3 = 114, "l3"; L4 = 115, "l4";
+    L5 = 116, "l5"; L6 = 117, "l6"; L7 = 118, "l7"; L8 = 119, "l8"; L9 = 120, "l9";
+    M0 = 121, "m0"; M1 = 122, "m1"; M2 = 123, "m2"; M3 = 124, "m3"; M4 = 125, "m4";
+    M5 = 126, "m5"; M6 = 127, "m6"; M7 = 128, "m7"; M8 = 129, "m8"; M9 = 130, "m9";
+    N0 = 131, "n0"; N1 = 132, "n1"; N2 = 133, "n2"; N3 = 134, "n3"; N4 = 135, "n4";
+    N5 = 136, "n5"; N6 = 137, "n6"; N7 = 138, "n7"; N8 = 139, "n8"; N9 = 140, "n9";
+    O0 = 141, "o0"; O1 = 142, "o1"; O2 = 143, "o2"; O3 = 144, "o3"; O4 = 145, "o4";
+    O5 = 146, "o5"; O6 = 147, "o6"; O7 = 148, "o7"; O8 = 149, "o8"; O9 = 150, "o9";
+    P0 = 151, "p0"; P1 = 152, "p1"; P2 = 153, "p2"; P3 = 154, "p3"; P4 = 155, "p4";
+    P5 = 156, "p5"; P6 = 157, "p6"; P7 = 158, "p7"; P8 = 159, "p8"; P9 = 160, "p9";
+    Q0 = 161, "q0"; Q1 = 162, "q1"; Q2 = 163, "q2"; Q3 = 164, "q3"; Q4 = 165, "q4";
+    Q5 = 166, "q5"; Q6 = 167, "q6"; Q7 = 168, "q7"; Q8 = 169, "q8"; Q9 = 170, "q9";
+    R0 = 171, "r0"; R1 = 172, "r1"; R2 = 173, "r2"; R3 = 174, "r3"; R4 = 175, "r4";
+    R5 = 176, "r5"; R6 = 177, "r6"; R7 = 178, "r7"; R8 = 179, "r8"; R9 = 180, "r9";
+    S0 = 181, "s0"; S1 = 182, "s1"; S2 = 183, "s2"; S3 = 184, "s3"; S4 = 185, "s4";
+    S5 = 186, "s5"; S6 = 187, "s6"; S7 = 188, "s7"; S8 = 189, "s8"; S9 = 190, "s9";
+    T0 = 191, "t0"; T1 = 192, "t1"; T2 = 193, "t2"; T3 = 194, "t3"; T4 = 195, "t4";
+    T5 = 196, "t5"; T6 = 197, "t6"; T7 = 198, "t7"; T8 = 199, "t8"; T9 = 200, "t9";
+    U0 = 201, "u0"; U1 = 202, "u1"; U2 = 203, "u2"; U3 = 204, "u3"; U4 = 205, "u4";
+    U5 = 206, "u5"; U6 = 207, "u6"; U7 = 208, "u7"; U8 = 209, "u8"; U9 = 210, "u9";
+    V0 = 211, "v0"; V1 = 212, "v1"; V2 = 213, "v2"; V3 = 214, "v3"; V4 = 215, "v4";
+    V5 = 216, "v5"; V6 = 217, "v6"; V7 = 218, "v7"; V8 = 219, "v8"; V9 = 220, "v9";
+    W0 = 221, "w0"; W1 = 222, "w1"; W2 = 223, "w2"; W3 = 224, "w3"; W4 = 225, "w4";
+    W5 = 226, "w5"; W6 = 227, "w6"; W7 = 228, "w7"; W8 = 229, "w8"; W9 = 230, "w9";
+    X0 = 231, "x0"; X1 = 232, "x1"; X2 = 233, "x2"; X3 = 234, "x3"; X4 = 235, "x4";
+    X5 = 236, "x5"; X6 = 237, "x6"; X7 = 238, "x7"; X8 = 239, "x8"; X9 = 240, "x9";
+    Y0 = 241, "y0"; Y1 = 242, "y1"; Y2 = 243, "y2"; Y3 = 244, "y3"; Y4 = 245, "y4";
+    Y5 = 246, "y5"; Y6 = 247, "y6"; Y7 = 248, "y7"; Y8 = 249, "y8"; Y9 = 250, "y9";
+    Z0 = 251, "z0"; Z1 = 252, "z1"; Z2 = 253, "z2"; Z3 = 254, "z3"; Z4 = 255, "z4";
+    Z5 = 256, "z5"; Z6 = 257, "z6"; Z7 = 258, "z7"; Z8 = 259, "z8"; Z9 = 260, "z9";
+    AA0 = 261, "aa0"; AA1 = 262, "aa1"; AA2 = 263, "aa2"; AA3 = 264, "aa3"; AA4 = 265, "aa4";
+    AA5 = 266, "aa5"; AA6 = 267, "aa6"; AA7 = 268, "aa7"; AA8 = 269, "aa8"; AA9 = 270, "aa9";
+    AB0 = 271, "ab0"; AB1 = 272, "ab1"; AB2 = 273, "ab2"; AB3 = 274, "ab3"; AB4 = 275, "ab4";
+    AB5 = 276, "ab5"; AB6 = 277, "ab6"; AB7 = 278, "ab7"; AB8 = 279, "ab8"; AB9 = 280, "ab9";
+    AC0 = 281, "ac0"; AC1 = 282, "ac1"; AC2 = 283, "ac2"; AC3 = 284, "ac3"; AC4 = 285, "ac4";
+    AC5 = 286, "ac5"; AC6 = 287, "ac6"; AC7 = 288, "ac7"; AC8 = 289, "ac8"; AC9 = 290, "ac9";
+    AD0 = 291, "ad0"; AD1 = 292, "ad1"; AD2 = 293, "ad2"; AD3 = 294, "ad3"; AD4 = 295, "ad4";
+    AD5 = 296, "ad5"; AD6 = 297, "ad6"; AD7 = 298, "ad7";
+}
+
+#[test]
+fn test_attribute_get() {
+    assert_eq!(Large::First(0).get_id(), 0);
+    assert_eq!(Large::A1.get_id(), 2);
+    assert_eq!(Large::AD7.get_id(), 298);
+    assert_eq!(Large::Last { field: 0 }.get_id(), 299);
+}
+
+#[test]
+fn test_attribute_default() {
+    assert_eq!(Large::B2.get_name(), "b2");
+    assert_eq!(Large::First(0).get_name(), "unnamed");
+    assert_eq!(Large::Last { field: 0 }.get_name(), "unnamed");
+}
+
+#[test]
+fn test_attribute_options() {
+    assert_eq!(Large::A2.get_weight(), Some(30));
+    assert_eq!(Large::Last { field: 0 }.get_weight(), None);
+}
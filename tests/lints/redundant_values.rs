@@ -0,0 +1,46 @@
+#![deny(deprecated)]
+
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub a: Option<usize>)]
+#[attr(pub b: u32)]
+#[attr(pub c: &'static str = "c")]
+#[attr(pub d: Option<char> = Some('d'))]
+#[attr(pub e: Option<u8>)]
+#[attr(pub f: &'static str)]
+// a string isn't a reference, even when it looks like one once expanded
+#[attr(pub g: &'static str)]
+#[attr(
+    #[field]
+    pub label: &'static str
+)]
+enum Enum {
+    #[attr(b = 1, c = "c", e = Some(1), f = "f", g = "__custom_attrs_attr_g")]
+    Variant1(#[attr(label = "field")] u32),
+
+    #[attr(b = 1, d = 'd', f = "f", g = "__custom_attrs_attr_g")]
+    Variant2(#[attr(label = "field")] u32),
+}
+
+#[derive(CustomAttrs)]
+#[attr(pub a: Option<usize>)]
+#[attr(pub b: Option<u32> = Some(2))]
+struct Struct {
+    field: usize,
+}
+
+// the warnings are silenced by the lint attributes of the type
+#[derive(CustomAttrs)]
+#[attr(pub a: Option<usize>)]
+#[attr(pub b: u32)]
+#[allow(deprecated)]
+enum Allowed {
+    #[attr(b = 1)]
+    Variant1,
+
+    #[attr(b = 1)]
+    Variant2,
+}
+
+fn main() {}
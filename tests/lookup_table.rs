@@ -0,0 +1,42 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[table]
+    pub weight: u32
+)]
+#[attr(
+    #[table, const_fn]
+    pub name: Option<&'static str>
+)]
+#[attr(
+    #[table]
+    pub cost: f32 = 1.0
+)]
+enum Item {
+    #[attr(weight = 3, name = "sword")]
+    Sword,
+
+    #[attr(weight = 1, cost = 0.5)]
+    Potion,
+
+    #[attr(weight = 10, name = "shield")]
+    Shield,
+}
+
+const SWORD_NAME: Option<&str> = Item::Sword.get_name();
+
+#[test]
+fn test_table_values() {
+    assert_eq!(Item::WEIGHT_TABLE, [3, 1, 10]);
+    assert_eq!(Item::NAME_TABLE, [Some("sword"), None, Some("shield")]);
+    assert_eq!(Item::COST_TABLE, [1.0, 0.5, 1.0]);
+}
+
+#[test]
+fn test_table_getters() {
+    assert_eq!(Item::Potion.get_weight(), 1);
+    assert_eq!(Item::Shield.get_name(), Some("shield"));
+    assert_eq!(Item::Potion.get_cost(), 0.5);
+    assert_eq!(SWORD_NAME, Some("sword"));
+}
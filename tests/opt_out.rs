@@ -0,0 +1,68 @@
+use custom_attrs::{AttrValue, CustomAttrs, MissingAttr};
+
+#[derive(CustomAttrs, Debug, PartialEq)]
+#[attr(
+    #[reverse]
+    pub code: u32
+)]
+#[attr(pub label: &'static str = "unknown")]
+#[attr(pub hint: Option<&'static str>)]
+#[attr(
+    #[const_fn]
+    pub weight: u8
+)]
+enum Command {
+    #[attr(code = 1, label = "open", weight = 3)]
+    Open,
+
+    #[attr(code = 2, weight = !, hint = "may fail")]
+    Save,
+
+    #[attr(skip)]
+    Internal,
+
+    #[allow(unused)]
+    #[attr(skip, code = 4)]
+    Debug(u32),
+}
+
+const OPEN_WEIGHT: Result<u8, MissingAttr> = Command::Open.try_get_weight();
+
+#[test]
+fn test_try_getter() {
+    assert_eq!(OPEN_WEIGHT, Ok(3));
+    assert_eq!(
+        Command::Save.try_get_weight().map_err(|e| (e.variant(), e.attribute())),
+        Err(("Save", "weight"))
+    );
+
+    assert_eq!(Command::Open.try_get_code(), Ok(1));
+    assert_eq!(Command::Debug(0).try_get_code(), Ok(4));
+
+    let error = Command::Internal.try_get_code().unwrap_err();
+    assert_eq!(error.variant(), "Internal");
+    assert_eq!(error.attribute(), "code");
+    assert_eq!(error.to_string(), "`Internal` has no value for `code`.");
+}
+
+#[test]
+fn test_skip_default_and_optional() {
+    // `skip` doesn't opt out of the attributes with a default value, nor of the optional ones
+    assert_eq!(Command::Internal.get_label(), "unknown");
+    assert_eq!(Command::Save.get_label(), "unknown");
+    assert_eq!(Command::Internal.get_hint(), None);
+}
+
+#[test]
+fn test_reverse_and_iteration() {
+    assert_eq!(Command::from_code(2), Some(Command::Save));
+
+    let weights = Command::iter_weight().collect::<Vec<_>>();
+    assert_eq!(weights, [(Command::Open, 3)]);
+}
+
+#[test]
+fn test_dynamic_access() {
+    assert!(matches!(Command::Open.attr("weight"), Some(AttrValue::UInt(3))));
+    assert!(Command::Save.attr("weight").is_none());
+}
@@ -0,0 +1,56 @@
+use custom_attrs::CustomAttrs;
+
+type MaybeId = Option<u32>;
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[optional]
+    pub id: MaybeId
+)]
+#[attr(
+    #[required]
+    pub parent: Option<&'static str>
+)]
+#[attr(
+    #[no_wrap]
+    pub limit: Option<Option<u32>>
+)]
+enum Node {
+    #[attr(id = 1, parent = None, limit = Some(None))]
+    Root,
+
+    #[attr(parent = Some("root"), limit = None)]
+    Child,
+
+    #[attr(parent = Some("child"), limit = Some(Some(3)))]
+    Leaf,
+}
+
+#[test]
+fn test_optional_alias() {
+    assert_eq!(Node::Root.get_id(), Some(1));
+    assert_eq!(Node::Child.get_id(), None);
+}
+
+#[test]
+fn test_required_option() {
+    assert_eq!(Node::Root.get_parent(), None);
+    assert_eq!(Node::Leaf.get_parent(), Some("child"));
+}
+
+#[test]
+fn test_no_wrap() {
+    assert_eq!(Node::Root.get_limit(), Some(None));
+    assert_eq!(Node::Child.get_limit(), None);
+    assert_eq!(Node::Leaf.get_limit(), Some(Some(3)));
+}
+
+#[test]
+fn test_attribute_info() {
+    let optional = Node::attribute_info()
+        .iter()
+        .map(|info| info.is_optional())
+        .collect::<Vec<_>>();
+
+    assert_eq!(optional, [true, false, true]);
+}
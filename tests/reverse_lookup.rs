@@ -0,0 +1,51 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs, Debug, PartialEq)]
+#[attr(
+    #[reverse]
+    pub code: u32
+)]
+#[attr(
+    #[reverse]
+    pub short_name: Option<&'static str>
+)]
+#[attr(
+    #[reverse]
+    pub wire_id: i8 = -1
+)]
+enum Error {
+    #[attr(code = 404, short_name = "not_found", wire_id = 1)]
+    NotFound,
+
+    #[attr(code = 500, short_name = "internal")]
+    Internal,
+
+    #[allow(unused)]
+    #[attr(code = 418)]
+    Other(u32),
+}
+
+#[test]
+fn test_reverse_lookup() {
+    assert_eq!(Error::from_code(404), Some(Error::NotFound));
+    assert_eq!(Error::from_code(500), Some(Error::Internal));
+    assert_eq!(Error::from_code(0), None);
+}
+
+#[test]
+fn test_reverse_lookup_with_fields() {
+    assert_eq!(Error::from_code(418), None);
+}
+
+#[test]
+fn test_reverse_lookup_options() {
+    assert_eq!(Error::from_short_name(Some("not_found")), Some(Error::NotFound));
+    assert_eq!(Error::from_short_name(Some("internal")), Some(Error::Internal));
+    assert_eq!(Error::from_short_name(None), None);
+}
+
+#[test]
+fn test_reverse_lookup_default() {
+    assert_eq!(Error::from_wire_id(1), Some(Error::NotFound));
+    assert_eq!(Error::from_wire_id(-1), Some(Error::Internal));
+}
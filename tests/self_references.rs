@@ -19,6 +19,53 @@ enum Enum {
     },
 }
 
+#[derive(CustomAttrs)]
+#[attr(pub len: usize = #self.0.len())]
+enum WithDefault {
+    Name(String),
+    List(Vec<u8>, bool),
+
+    #[attr(len = 0)]
+    Empty,
+}
+
+#[derive(CustomAttrs)]
+#[attr(pub inner: u32)]
+enum Nested {
+    #[attr(inner = #self.0.1)]
+    Pair((u8, u32), bool),
+
+    #[attr(inner = #self.1.0.1 + #self.0)]
+    Deep(u32, ((u8, u32), u8)),
+
+    #[attr(inner = #self.point.1)]
+    Named { point: (u32, u32) },
+}
+
+fn weight(value: &Whole) -> usize {
+    match value {
+        Whole::Empty => 0,
+        Whole::Pair(a, b) => (a + b) as usize,
+        Whole::Named { x } => *x as usize,
+    }
+}
+
+#[derive(CustomAttrs)]
+#[attr(pub weight: usize = weight(#self))]
+#[attr(pub payload: String = format!("{:?}", #fields))]
+enum Whole {
+    Empty,
+    Pair(u8, u8),
+    Named { x: u32 },
+}
+
+#[derive(CustomAttrs)]
+#[attr(pub total: u32 = { let (a, b) = #fields; a + b })]
+struct Sum {
+    a: u32,
+    b: u32,
+}
+
 const VARIANT1: Enum = Enum::Variant1(0, 1);
 const VARIANT2: Enum = Enum::Variant2 {
     field1: 2,
@@ -45,3 +92,33 @@ fn test_attribute_option_get() {
     assert_eq!(VARIANT1.get_b(), Some(1));
     assert_eq!(VARIANT2.get_b(), Some(2));
 }
+
+#[test]
+fn test_default_value_self_reference() {
+    assert_eq!(WithDefault::Name(String::from("abc")).get_len(), 3);
+    assert_eq!(WithDefault::List(vec![1, 2], true).get_len(), 2);
+    assert_eq!(WithDefault::Empty.get_len(), 0);
+}
+
+#[test]
+fn test_chained_tuple_index() {
+    assert_eq!(Nested::Pair((1, 2), true).get_inner(), 2);
+    assert_eq!(Nested::Deep(1, ((2, 3), 4)).get_inner(), 4);
+    assert_eq!(Nested::Named { point: (5, 6) }.get_inner(), 6);
+}
+
+#[test]
+fn test_whole_variant_reference() {
+    assert_eq!(Whole::Empty.get_weight(), 0);
+    assert_eq!(Whole::Pair(1, 2).get_weight(), 3);
+    assert_eq!(Whole::Named { x: 4 }.get_weight(), 4);
+}
+
+#[test]
+fn test_all_fields_reference() {
+    assert_eq!(Whole::Empty.get_payload(), "()");
+    assert_eq!(Whole::Pair(1, 2).get_payload(), "(1, 2)");
+    assert_eq!(Whole::Named { x: 4 }.get_payload(), "(4,)");
+
+    assert_eq!(Sum { a: 1, b: 2 }.get_total(), 3);
+}
@@ -0,0 +1,43 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(pub name: &'static str = "record")]
+#[attr(pub version: u32 = 3)]
+#[attr(pub table: Option<&'static str> = "records")]
+#[attr(pub id_len: usize = #self.id.len())]
+struct Record {
+    id: String,
+}
+
+#[derive(CustomAttrs)]
+#[attr(pub first: u32 = *#self.0)]
+#[attr(
+    #[function = "second_getter"]
+    pub second: Option<u32> = 4
+)]
+struct Tuple(u32);
+
+#[test]
+fn test_attribute_get() {
+    let record = Record {
+        id: String::from("abcd"),
+    };
+
+    assert_eq!(record.get_name(), "record");
+    assert_eq!(record.get_version(), 3);
+    assert_eq!(record.get_id_len(), 4);
+    assert_eq!(Tuple(5).get_first(), 5);
+}
+
+#[test]
+fn test_attribute_associated() {
+    assert_eq!(Record::name(), "record");
+    assert_eq!(Record::version(), 3);
+    assert_eq!(Tuple::second(), Some(4));
+}
+
+#[test]
+fn test_attribute_options() {
+    assert_eq!(Record::table(), Some("records"));
+    assert_eq!(Tuple(1).second_getter(), Some(4));
+}
@@ -0,0 +1,86 @@
+use std::{borrow::Cow, fmt::Display};
+
+use custom_attrs::CustomAttrs;
+
+#[derive(Debug, PartialEq)]
+pub struct Code(u32);
+
+#[derive(CustomAttrs)]
+#[attr(
+    #[convert = "into"]
+    pub name: String
+)]
+#[attr(
+    #[convert = "into"]
+    pub alias: Option<Cow<'static, str>>
+)]
+#[attr(
+    #[wrap = Box::new]
+    pub display: Box<dyn Display> = "none"
+)]
+#[attr(
+    #[wrap = Code]
+    pub code: Code = 0
+)]
+#[attr(pub label: String = format!("{} ({:?})", #attr.name, #attr.code))]
+enum Item {
+    #[attr(name = "sword", alias = "blade", display = 3, code = 12)]
+    Sword,
+
+    #[attr(name = "potion", alias = "flask")]
+    Potion,
+
+    #[attr(name = String::from("shield"), alias = None)]
+    Shield,
+}
+
+#[derive(CustomAttrs, Debug, PartialEq)]
+#[attr(
+    #[convert = "into", reverse]
+    pub name: String
+)]
+#[attr(
+    #[wrap = Code, reverse]
+    pub code: Code = 0
+)]
+enum Command {
+    #[attr(name = "open", code = 1)]
+    Open,
+
+    #[attr(name = "close")]
+    Close,
+}
+
+#[test]
+fn test_convert_into() {
+    assert_eq!(Item::Sword.get_name(), "sword");
+    assert_eq!(Item::Shield.get_name(), "shield");
+
+    assert_eq!(Item::Sword.get_alias(), Some(Cow::Borrowed("blade")));
+    assert_eq!(Item::Potion.get_alias(), Some(Cow::Borrowed("flask")));
+    assert_eq!(Item::Shield.get_alias(), None);
+}
+
+#[test]
+fn test_wrap() {
+    assert_eq!(Item::Sword.get_display().to_string(), "3");
+    assert_eq!(Item::Potion.get_display().to_string(), "none");
+
+    assert_eq!(Item::Sword.get_code(), Code(12));
+    assert_eq!(Item::Shield.get_code(), Code(0));
+}
+
+#[test]
+fn test_attribute_references() {
+    assert_eq!(Item::Sword.get_label(), "sword (Code(12))");
+    assert_eq!(Item::Potion.get_label(), "potion (Code(0))");
+}
+
+#[test]
+fn test_reverse_lookup() {
+    assert_eq!(Command::from_name("open".into()), Some(Command::Open));
+    assert_eq!(Command::from_name("save".into()), None);
+
+    assert_eq!(Command::from_code(Code(1)), Some(Command::Open));
+    assert_eq!(Command::from_code(Code(0)), Some(Command::Close));
+}
@@ -0,0 +1,75 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs, Debug, PartialEq)]
+#[attr(pub code: u32)]
+#[attr(pub label: Option<&'static str>)]
+#[attr(pub size: usize)]
+enum Enum {
+    #[attr(code = 1, label = "first", size = 0)]
+    Variant1,
+
+    #[attr(code = 2, size = 0)]
+    Variant2,
+
+    #[allow(unused)]
+    #[attr(code = 3, size = *#self.0)]
+    Variant3(usize),
+}
+
+#[derive(CustomAttrs, Debug, PartialEq)]
+#[attr(pub len: usize = #self.0.len())]
+enum Borrowing<'a> {
+    #[attr(len = 0)]
+    Empty,
+
+    #[allow(unused)]
+    Name(&'a str),
+}
+
+#[derive(CustomAttrs, Debug, PartialEq)]
+#[attr(pub code: u32 = 0)]
+enum Generic<T> {
+    #[attr(code = 1)]
+    Variant1,
+
+    #[allow(unused)]
+    Variant2(T),
+}
+
+#[test]
+fn test_variants() {
+    assert_eq!(Enum::VARIANTS, &[Enum::Variant1, Enum::Variant2]);
+}
+
+#[test]
+fn test_variants_generic() {
+    assert_eq!(Generic::<String>::VARIANTS, &[Generic::Variant1]);
+    assert_eq!(Borrowing::<'static>::VARIANTS, &[Borrowing::Empty]);
+}
+
+#[test]
+fn test_iter_attribute() {
+    let codes = Enum::iter_code().collect::<Vec<_>>();
+    assert_eq!(codes, vec![(Enum::Variant1, 1), (Enum::Variant2, 2)]);
+
+    let labels = Enum::iter_label().collect::<Vec<_>>();
+    assert_eq!(
+        labels,
+        vec![(Enum::Variant1, Some("first")), (Enum::Variant2, None)]
+    );
+}
+
+#[test]
+fn test_iter_attribute_self_references() {
+    let sizes = Enum::iter_size().collect::<Vec<_>>();
+    assert_eq!(sizes, vec![(Enum::Variant1, 0), (Enum::Variant2, 0)]);
+
+    let lens = Borrowing::iter_len().collect::<Vec<_>>();
+    assert_eq!(lens, vec![(Borrowing::Empty, 0)]);
+}
+
+#[test]
+fn test_iter_attribute_generic() {
+    let codes = Generic::<String>::iter_code().collect::<Vec<_>>();
+    assert_eq!(codes, vec![(Generic::Variant1, 1)]);
+}
@@ -0,0 +1,50 @@
+use custom_attrs::CustomAttrs;
+
+#[derive(CustomAttrs)]
+#[attr(name: &'static str = #variant.name)]
+#[attr(snake: &'static str = #variant.snake_name)]
+#[attr(kebab: &'static str = #variant.kebab_name)]
+#[attr(upper_snake: &'static str = #variant.upper_snake_name)]
+#[attr(camel: &'static str = #variant.camel_name)]
+#[attr(index: usize = #variant.index)]
+#[attr(code: u8 = #variant.discriminant)]
+#[repr(u8)]
+#[allow(unused)]
+enum Enum {
+    HttpServer = 3,
+    ReadOnly,
+
+    #[attr(name = "custom")]
+    WithFields {
+        field: u32,
+    } = 10,
+
+    #[attr(kebab = concat!("x-", #variant.kebab_name))]
+    Last,
+}
+
+#[test]
+fn variant_name() {
+    assert_eq!(Enum::HttpServer.get_name(), "HttpServer");
+    assert_eq!(Enum::WithFields { field: 0 }.get_name(), "custom");
+}
+
+#[test]
+fn variant_case_conversions() {
+    assert_eq!(Enum::HttpServer.get_snake(), "http_server");
+    assert_eq!(Enum::ReadOnly.get_kebab(), "read-only");
+    assert_eq!(Enum::ReadOnly.get_upper_snake(), "READ_ONLY");
+    assert_eq!(Enum::HttpServer.get_camel(), "httpServer");
+    assert_eq!(Enum::Last.get_kebab(), "x-last");
+}
+
+#[test]
+fn variant_index_and_discriminant() {
+    assert_eq!(Enum::HttpServer.get_index(), 0);
+    assert_eq!(Enum::Last.get_index(), 3);
+
+    assert_eq!(Enum::HttpServer.get_code(), 3);
+    assert_eq!(Enum::ReadOnly.get_code(), 4);
+    assert_eq!(Enum::WithFields { field: 0 }.get_code(), 10);
+    assert_eq!(Enum::Last.get_code(), 11);
+}